@@ -1,6 +1,7 @@
 """Tasks API routes."""
 
 import json
+import os
 import sys
 from pathlib import Path
 from typing import List, Optional, Dict, Any
@@ -64,8 +65,18 @@ from models.schemas import TaskSummary, TaskDetail, PhaseData, AgentData, Tracke
 
 router = APIRouter()
 
+# Workspace base chosen for this server, e.g. by the desktop app's set_workspace
+WORKSPACE_ENV = "CLAUDE_ORCHESTRATOR_WORKSPACE"
+
+
+def _configured_workspace_base() -> Optional[Path]:
+    """Return the workspace base set in CLAUDE_ORCHESTRATOR_WORKSPACE, if any."""
+    value = os.getenv(WORKSPACE_ENV)
+    return Path(value).expanduser() if value else None
+
+
 # Base workspace path
-WORKSPACE_BASE = Path.home() / ".agent-workspace"
+WORKSPACE_BASE = _configured_workspace_base() or Path.home() / ".agent-workspace"
 LOCAL_WORKSPACE_BASE = orchestrator_path / ".agent-workspace"
 
 ALLOWED_AGENT_STATUSES = {
@@ -120,7 +131,12 @@ def _normalize_agent_status(status: Any, progress: Any = None) -> str:
 
 
 def _iter_workspace_bases() -> List[Path]:
-    """Return workspace bases to search (global registry + fallbacks)."""
+    """Return workspace bases to search (the configured base, else global registry + fallbacks)."""
+    # A base set in the environment is the only one this server serves
+    configured = _configured_workspace_base()
+    if configured is not None:
+        return [configured]
+
     # Try global SQLite registry first (cross-project discovery)
     try:
         from orchestrator import global_registry
@@ -513,6 +529,7 @@ async def list_tasks(
                             current_phase=current_phase,
                             agent_count=total_agents,
                             active_agents=task_data.get('active_agents', 0),
+                            failed_agents=task_data.get('failed_agents', 0),
                             progress=progress
                         ))
                 except Exception as e:
@@ -608,6 +625,7 @@ async def list_tasks(
             current_phase = None
             agent_count = 0
             active_agents = 0
+            failed_agents = 0
             progress = 0
             created_at = task_info.get("created_at")
             description = task_info.get("description", "")
@@ -651,6 +669,7 @@ async def list_tasks(
                                     )
                                     agent_count = task_counts["total_agents"]
                                     active_agents = task_counts["active_agents"]
+                                    failed_agents = task_counts["failed_agents"]
                                     completed_agents = task_counts["completed_agents"]
                                     # Calculate progress based on completion
                                     if agent_count > 0:
@@ -744,6 +763,7 @@ async def list_tasks(
                 current_phase=current_phase,
                 agent_count=agent_count,
                 active_agents=active_agents,
+                failed_agents=failed_agents,
                 progress=progress
             ))
 
@@ -754,6 +774,41 @@ async def list_tasks(
         raise HTTPException(status_code=500, detail=str(e))
 
 
+@router.post("/health-scan")
+async def trigger_health_scan(task_id: Optional[str] = Query(None, description="Task to scan; all active tasks if omitted")):
+    """Run one agent health scan, like the MCP `trigger_health_scan` tool, without starting the daemon."""
+    if not HAS_STATE_DB:
+        raise HTTPException(status_code=501, detail="Health scans need the orchestrator modules")
+
+    from fastapi.concurrency import run_in_threadpool
+    from orchestrator.health_daemon import HealthDaemon
+
+    def scan() -> List[str]:
+        scanned: List[str] = []
+        for base in _iter_workspace_bases():
+            if not base.exists():
+                continue
+            if task_id:
+                task_ids = [task_id] if (base / task_id).is_dir() else []
+            else:
+                task_ids = [t["task_id"] for t in state_db.get_all_tasks(
+                    workspace_base=str(base), limit=1000, offset=0, status_filter="ACTIVE"
+                )]
+            daemon = HealthDaemon(str(base))
+            for tid in task_ids:
+                try:
+                    if daemon.scan_task(tid):
+                        scanned.append(tid)
+                except Exception as e:
+                    print(f"[API] Health scan of {tid} failed: {e}")
+        return scanned
+
+    scanned = await run_in_threadpool(scan)
+    if task_id and not scanned:
+        raise HTTPException(status_code=404, detail=f"Task {task_id} not found")
+    return {"success": True, "scanned_tasks": scanned}
+
+
 @router.get("/{task_id}", response_model=TaskDetail)
 async def get_task(task_id: str):
     """Get detailed information about a specific task."""
@@ -870,6 +925,26 @@ async def get_task_registry(task_id: str):
         raise HTTPException(status_code=500, detail=str(e))
 
 
+@router.get("/{task_id}/handovers")
+async def get_task_handovers(task_id: str) -> List[Dict[str, Any]]:
+    """Get the phase handover documents of a task."""
+    if not HAS_STATE_DB:
+        raise HTTPException(status_code=501, detail="Handovers need the orchestrator modules")
+
+    task_workspace = find_task_workspace(task_id)
+    if not task_workspace:
+        raise HTTPException(status_code=404, detail=f"Task {task_id} not found")
+
+    try:
+        return state_db.get_handovers_for_task(
+            workspace_base=str(Path(task_workspace).parent),
+            task_id=task_id
+        )
+    except Exception as e:
+        print(f"[API] Error getting handovers for task {task_id}: {e}")
+        raise HTTPException(status_code=500, detail=str(e))
+
+
 @router.get("/summary/dashboard")
 async def get_dashboard_summary():
     """Get dashboard summary with SQLite-derived counts from all projects."""
@@ -11,7 +11,7 @@ from typing import Dict, Any, Optional
 from fastapi import WebSocket, WebSocketDisconnect, status
 from fastapi.responses import JSONResponse
 
-from .manager import connection_manager, SubscriptionTarget
+from .manager import connection_manager, SubscriptionTarget, WILDCARD_ID, WILDCARD_TARGETS
 
 logger = logging.getLogger(__name__)
 
@@ -109,6 +109,8 @@ class WebSocketEndpoint:
             "target": "task" | "agent" | "logs" | "phase" | "tmux",
             "id": "entity_id"
         }
+
+        An id of "*" subscribes to every entity of a task, agent or phase target.
         """
         target = data.get("target")
         entity_id = data.get("id")
@@ -123,6 +125,10 @@ class WebSocketEndpoint:
             await self._send_error(client_id, f"Invalid subscription target: {target}. Valid targets: {valid_targets}")
             return
 
+        if entity_id == WILDCARD_ID and target not in WILDCARD_TARGETS:
+            await self._send_error(client_id, f"Wildcard id {WILDCARD_ID!r} is only allowed for {sorted(WILDCARD_TARGETS)}")
+            return
+
         # Process subscription
         success = await connection_manager.subscribe(client_id, target, entity_id)
 
@@ -15,6 +15,10 @@ from enum import Enum
 logger = logging.getLogger(__name__)
 
 
+# Entity ID that subscribes to every entity of a target
+WILDCARD_ID = "*"
+
+
 class SubscriptionTarget(Enum):
     """Types of entities clients can subscribe to"""
     TASK = "task"
@@ -24,6 +28,10 @@ class SubscriptionTarget(Enum):
     TMUX = "tmux"
 
 
+# Targets WILDCARD_ID may be used with; logs and tmux stream one entity each
+WILDCARD_TARGETS = {SubscriptionTarget.TASK.value, SubscriptionTarget.AGENT.value, SubscriptionTarget.PHASE.value}
+
+
 class EventType(Enum):
     """Server-to-client event types"""
     TASK_UPDATE = "task_update"
@@ -145,6 +153,14 @@ class ConnectionManager:
                 })
                 return False
 
+            if entity_id == WILDCARD_ID and target not in WILDCARD_TARGETS:
+                logger.warning(f"Wildcard subscription to {target} rejected")
+                await self._send_to_client(client_id, {
+                    "type": EventType.ERROR.value,
+                    "message": f"Wildcard id {WILDCARD_ID!r} is not allowed for {target}"
+                })
+                return False
+
             # Add to client's subscriptions
             connection.subscriptions[target].add(entity_id)
 
@@ -230,7 +246,8 @@ class ConnectionManager:
 
     async def send_to_subscription(self, target: str, entity_id: str, message: Dict[str, Any]):
         """
-        Send a message to all clients subscribed to a specific entity
+        Send a message to all clients subscribed to a specific entity,
+        or to every entity of the target
 
         Args:
             target: Subscription target type
@@ -238,9 +255,10 @@ class ConnectionManager:
             message: Message to send
         """
         key = self._make_subscription_key(target, entity_id)
+        wildcard_key = self._make_subscription_key(target, WILDCARD_ID)
 
         async with self._lock:
-            client_ids = self._subscription_index.get(key, set()).copy()
+            client_ids = self._subscription_index.get(key, set()) | self._subscription_index.get(wildcard_key, set())
 
         if not client_ids:
             logger.debug(f"No subscribers for {target}:{entity_id}")
@@ -1,6 +1,8 @@
 """FastAPI backend server for Claude Orchestrator Dashboard."""
 
+import os
 import time
+import sqlite3
 import sys
 from pathlib import Path
 from datetime import datetime
@@ -51,6 +53,10 @@ except ImportError:
 # Server start time for health check
 server_start_time = time.time()
 
+# The desktop app identifies its backend by this name, not by version
+SERVICE_NAME = "Claude Orchestrator Dashboard API"
+API_VERSION = "1.0.0"
+
 
 @asynccontextmanager
 async def lifespan(app: FastAPI):
@@ -73,9 +79,9 @@ async def lifespan(app: FastAPI):
 
 # Create FastAPI app
 app = FastAPI(
-    title="Claude Orchestrator Dashboard API",
+    title=SERVICE_NAME,
     description="REST API and WebSocket server for Claude Orchestrator Dashboard",
-    version="1.0.0",
+    version=API_VERSION,
     lifespan=lifespan
 )
 
@@ -108,25 +114,85 @@ async def health_check():
     if HealthResponse:
         return HealthResponse(
             status="healthy",
-            version="1.0.0",
+            service=SERVICE_NAME,
+            version=API_VERSION,
             uptime=time.time() - server_start_time,
             timestamp=datetime.now()
         )
     else:
         return {
             "status": "healthy",
-            "version": "1.0.0",
+            "service": SERVICE_NAME,
+            "version": API_VERSION,
             "uptime": time.time() - server_start_time,
             "timestamp": datetime.now().isoformat()
         }
 
 
+def _check_workspace_base(workspace_base: Path) -> dict:
+    """Check that a workspace base and its state database are reachable."""
+    db_path = workspace_base / 'registry' / 'state.sqlite3'
+    checks = {
+        "workspace": {"ok": workspace_base.is_dir(), "path": str(workspace_base)},
+    }
+    if not checks["workspace"]["ok"]:
+        checks["workspace"]["error"] = "directory not found"
+
+    try:
+        # Read-only so a health probe can never create or lock the database
+        conn = sqlite3.connect(f"{db_path.absolute().as_uri()}?mode=ro", uri=True, timeout=2)
+        try:
+            conn.execute("SELECT count(*) FROM sqlite_master").fetchone()
+        finally:
+            conn.close()
+        checks["registry"] = {"ok": True, "path": str(db_path)}
+    except sqlite3.Error as e:
+        checks["registry"] = {"ok": False, "path": str(db_path), "error": str(e)}
+    return checks
+
+
+@app.get("/health/deep", tags=["health"])
+async def deep_health_check():
+    """Deep health check: verify the workspace bases the task routes search and their state databases.
+
+    Healthy as long as one base and its database are reachable; every base is reported.
+    """
+    if tasks:
+        workspace_bases = tasks._iter_workspace_bases()
+    else:
+        workspace_bases = [Path(os.getenv(
+            'CLAUDE_ORCHESTRATOR_WORKSPACE',
+            str(Path.home() / '.agent-workspace')
+        )).expanduser()]
+
+    checks = {}
+    healthy = False
+    for index, workspace_base in enumerate(workspace_bases):
+        base_checks = _check_workspace_base(workspace_base)
+        healthy = healthy or all(check["ok"] for check in base_checks.values())
+        for name, check in base_checks.items():
+            checks[name if len(workspace_bases) == 1 else f"{name}[{index}]"] = check
+    if not workspace_bases:
+        checks["workspace"] = {"ok": False, "path": None, "error": "no workspace bases found"}
+
+    return JSONResponse(
+        status_code=200 if healthy else 503,
+        content={
+            "status": "healthy" if healthy else "unhealthy",
+            "service": SERVICE_NAME,
+            "version": API_VERSION,
+            "checks": checks,
+            "timestamp": datetime.now().isoformat()
+        }
+    )
+
+
 @app.get("/", tags=["root"])
 async def root():
     """Root endpoint."""
     return {
-        "message": "Claude Orchestrator Dashboard API",
-        "version": "1.0.0",
+        "message": SERVICE_NAME,
+        "version": API_VERSION,
         "docs": "/docs",
         "health": "/health",
         "websocket": "/ws"
@@ -103,6 +103,7 @@ class TaskSummary(BaseModel):
     current_phase: Optional[PhaseData] = None
     agent_count: int
     active_agents: int
+    failed_agents: int = 0
     progress: int = Field(ge=0, le=100)
 
 
@@ -238,6 +239,7 @@ class WSFindingReported(WSMessage):
 class HealthResponse(BaseModel):
     """Health check response."""
     status: str = "healthy"
+    service: str = "Claude Orchestrator Dashboard API"
     version: str = "1.0.0"
     uptime: float
     timestamp: datetime
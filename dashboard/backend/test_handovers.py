@@ -0,0 +1,94 @@
+#!/usr/bin/env python3
+"""
+Test script for the task handovers route.
+
+Run this from the dashboard/backend directory:
+    python test_handovers.py
+"""
+
+import os
+import sys
+import tempfile
+from pathlib import Path
+
+# Add current directory and the repository root to path
+BACKEND_DIR = Path(__file__).resolve().parent
+sys.path.insert(0, str(BACKEND_DIR))
+sys.path.insert(0, str(BACKEND_DIR.parent.parent))
+
+from orchestrator import state_db
+
+
+def create_task(base, task_id):
+    """Create a task workspace with one handover under `base`."""
+    workspace = os.path.join(base, task_id)
+    os.makedirs(workspace)
+    state_db.create_task_with_phases(
+        workspace_base=base,
+        task_id=task_id,
+        workspace=workspace,
+        description="Handover route test",
+        phases=[{"name": "Investigation"}, {"name": "Fix"}],
+    )
+    state_db.create_handover(
+        workspace_base=base,
+        task_id=task_id,
+        from_phase_index=0,
+        to_phase_index=1,
+        summary="Root cause found",
+        key_findings=["KeyError on missing phase_index"],
+    )
+    return workspace
+
+
+def test_handovers_route():
+    """GET /api/tasks/{task_id}/handovers returns the task's handovers, or 404."""
+    try:
+        from fastapi import FastAPI
+        from fastapi.testclient import TestClient
+        from api.routes import tasks
+    except ImportError as e:
+        print(f"   Skipped, FastAPI not available: {e}")
+        return
+
+    with tempfile.TemporaryDirectory() as base:
+        task_id = "TASK-20260104-150000-abc12345"
+        workspace = create_task(base, task_id)
+
+        find_task_workspace = tasks.find_task_workspace
+        tasks.find_task_workspace = lambda tid: workspace if tid == task_id else None
+        try:
+            app = FastAPI()
+            app.include_router(tasks.router, prefix="/api/tasks")
+            client = TestClient(app)
+
+            response = client.get(f"/api/tasks/{task_id}/handovers")
+            assert response.status_code == 200, response.text
+            handovers = response.json()
+            assert [h["summary"] for h in handovers] == ["Root cause found"]
+            assert handovers[0]["key_findings"] == ["KeyError on missing phase_index"]
+            print("   ✓ handovers of an existing task")
+
+            response = client.get("/api/tasks/TASK-missing/handovers")
+            assert response.status_code == 404, response.text
+            print("   ✓ 404 for an unknown task")
+        finally:
+            tasks.find_task_workspace = find_task_workspace
+
+
+def main():
+    """Run all tests."""
+    print("=" * 60)
+    print("Handovers Route Test")
+    print("=" * 60)
+
+    print("\n1. Testing /api/tasks/{task_id}/handovers...")
+    test_handovers_route()
+
+    print("\n" + "=" * 60)
+    print("All tests completed!")
+    print("=" * 60)
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,304 @@
+use reqwest::Url;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tracing::debug;
+
+use crate::models::{
+    AgentData, AgentFinding, AgentOutput, AgentProgress, Handover, LogEntry, PhaseRecord, TaskDetail, TaskSummary,
+    TmuxSessionInfo,
+};
+use crate::profiles::Backend;
+
+/// Timeout of a data request; reading large logs can take a while
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Error of a data command, serialized as `{ kind, message, status? }`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub(crate) enum ApiError {
+    /// No backend to ask, e.g. the window's profile is not active
+    Unavailable { message: String },
+    /// The backend could not be reached
+    Unreachable { message: String },
+    /// The backend answered with an error status
+    Status { status: u16, message: String },
+    /// The response doesn't match the models - the backend and app disagree
+    Decode { message: String },
+    /// Reading the state database failed in offline mode
+    Database { message: String },
+}
+
+impl From<String> for ApiError {
+    fn from(message: String) -> Self {
+        Self::Unavailable { message }
+    }
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unavailable { message }
+            | Self::Unreachable { message }
+            | Self::Decode { message }
+            | Self::Database { message } => f.write_str(message),
+            Self::Status { status, message } => write!(f, "{} ({})", message, status),
+        }
+    }
+}
+
+/// Body of FastAPI error responses.
+#[derive(Deserialize)]
+struct ErrorBody {
+    detail: serde_json::Value,
+}
+
+/// Query of `GET /api/tasks`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct TaskQuery {
+    pub status: Option<String>,
+    /// `YYYY-MM-DD`, `today`, `yesterday`, `week` or `all`; the backend defaults to `today`
+    pub since: Option<String>,
+    pub until: Option<String>,
+    /// Part of the project name
+    pub project: Option<String>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+/// Query of `GET /api/agents/{task_id}`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct AgentQuery {
+    pub status: Option<String>,
+    pub phase_index: Option<i64>,
+}
+
+/// Query of `GET /api/agents/{task_id}/{agent_id}/findings`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct FindingQuery {
+    pub severity: Option<String>,
+    pub finding_type: Option<String>,
+    pub limit: Option<u32>,
+}
+
+/// Query of `GET /api/agents/{task_id}/{agent_id}/logs`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct LogQuery {
+    pub log_type: Option<String>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+/// Query of `GET /api/agents/{task_id}/{agent_id}/output`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct OutputQuery {
+    /// `recent`, `full`, `compact` or `summary`
+    pub response_format: Option<String>,
+    pub recent_lines: Option<u32>,
+}
+
+/// Typed client for the dashboard REST API of one backend.
+pub(crate) struct ApiClient {
+    base: String,
+    http: reqwest::Client,
+}
+
+impl ApiClient {
+    pub(crate) fn new(backend: &Backend) -> Self {
+        Self {
+            base: backend.url(),
+            http: reqwest::Client::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    pub(crate) async fn tasks(&self, query: &TaskQuery) -> Result<Vec<TaskSummary>, ApiError> {
+        let query = [
+            ("status", query.status.clone()),
+            ("since", query.since.clone()),
+            ("until", query.until.clone()),
+            ("project", query.project.clone()),
+            ("limit", query.limit.map(|n| n.to_string())),
+            ("offset", query.offset.map(|n| n.to_string())),
+        ];
+        self.get(&["api", "tasks"], &query).await
+    }
+
+    pub(crate) async fn task(&self, task_id: &str) -> Result<TaskDetail, ApiError> {
+        self.get(&["api", "tasks", task_id], &[]).await
+    }
+
+    /// The task's `AGENT_REGISTRY.json` as stored, without a fixed shape.
+    pub(crate) async fn task_registry(&self, task_id: &str) -> Result<serde_json::Value, ApiError> {
+        self.get(&["api", "tasks", task_id, "registry"], &[]).await
+    }
+
+    pub(crate) async fn handovers(&self, task_id: &str) -> Result<Vec<Handover>, ApiError> {
+        self.get(&["api", "tasks", task_id, "handovers"], &[]).await
+    }
+
+    pub(crate) async fn agents(&self, task_id: &str, query: &AgentQuery) -> Result<Vec<AgentData>, ApiError> {
+        let query = [
+            ("status", query.status.clone()),
+            ("phase_index", query.phase_index.map(|n| n.to_string())),
+        ];
+        self.get(&["api", "agents", task_id], &query).await
+    }
+
+    pub(crate) async fn agent_progress(
+        &self,
+        task_id: &str,
+        agent_id: &str,
+        limit: Option<u32>,
+    ) -> Result<Vec<AgentProgress>, ApiError> {
+        let query = [("limit", limit.map(|n| n.to_string()))];
+        self.get(&["api", "agents", task_id, agent_id, "progress"], &query).await
+    }
+
+    pub(crate) async fn agent_findings(
+        &self,
+        task_id: &str,
+        agent_id: &str,
+        query: &FindingQuery,
+    ) -> Result<Vec<AgentFinding>, ApiError> {
+        let query = [
+            ("severity", query.severity.clone()),
+            ("finding_type", query.finding_type.clone()),
+            ("limit", query.limit.map(|n| n.to_string())),
+        ];
+        self.get(&["api", "agents", task_id, agent_id, "findings"], &query).await
+    }
+
+    pub(crate) async fn agent_logs(
+        &self,
+        task_id: &str,
+        agent_id: &str,
+        query: &LogQuery,
+    ) -> Result<Vec<LogEntry>, ApiError> {
+        let query = [
+            ("log_type", query.log_type.clone()),
+            ("limit", query.limit.map(|n| n.to_string())),
+            ("offset", query.offset.map(|n| n.to_string())),
+        ];
+        self.get(&["api", "agents", task_id, agent_id, "logs"], &query).await
+    }
+
+    pub(crate) async fn agent_output(
+        &self,
+        task_id: &str,
+        agent_id: &str,
+        query: &OutputQuery,
+    ) -> Result<AgentOutput, ApiError> {
+        let query = [
+            ("response_format", query.response_format.clone()),
+            ("recent_lines", query.recent_lines.map(|n| n.to_string())),
+        ];
+        self.get(&["api", "agents", task_id, agent_id, "output"], &query).await
+    }
+
+    pub(crate) async fn phases(&self, task_id: &str) -> Result<Vec<PhaseRecord>, ApiError> {
+        self.get(&["api", "phases", task_id], &[]).await
+    }
+
+    pub(crate) async fn tmux_sessions(&self) -> Result<Vec<TmuxSessionInfo>, ApiError> {
+        self.get(&["api", "tmux", "sessions"], &[]).await
+    }
+
+    /// GET the path made of `segments`, each percent-encoded, with the query
+    /// parameters that are set.
+    async fn get<T: DeserializeOwned>(
+        &self,
+        segments: &[&str],
+        query: &[(&str, Option<String>)],
+    ) -> Result<T, ApiError> {
+        let url = self.url(segments, query)?;
+        debug!(%url, "API request");
+        let response = self
+            .http
+            .get(url.clone())
+            .send()
+            .await
+            .map_err(|e| ApiError::Unreachable {
+                message: format!("Cannot reach {}: {}", self.base, e),
+            })?;
+
+        let status = response.status();
+        let body = response.bytes().await.map_err(|e| ApiError::Unreachable {
+            message: format!("Failed to read response from {}: {}", url, e),
+        })?;
+        if !status.is_success() {
+            let message = match serde_json::from_slice::<ErrorBody>(&body) {
+                Ok(ErrorBody {
+                    detail: serde_json::Value::String(detail),
+                }) => detail,
+                Ok(ErrorBody { detail }) => detail.to_string(),
+                Err(_) => String::from_utf8_lossy(&body).into_owned(),
+            };
+            return Err(ApiError::Status {
+                status: status.as_u16(),
+                message,
+            });
+        }
+        serde_json::from_slice(&body).map_err(|e| ApiError::Decode {
+            message: format!("Unexpected response from {}: {}", url.path(), e),
+        })
+    }
+
+    /// `segments` joined onto the base, after any path it has. Configured
+    /// backend URLs are origins (see `settings::normalize_url`).
+    fn url(&self, segments: &[&str], query: &[(&str, Option<String>)]) -> Result<Url, ApiError> {
+        let mut url = Url::parse(&self.base).map_err(|e| ApiError::Unavailable {
+            message: format!("Invalid backend URL {}: {}", self.base, e),
+        })?;
+        url.path_segments_mut()
+            .map_err(|_| ApiError::Unavailable {
+                message: format!("Invalid backend URL {}", self.base),
+            })?
+            .pop_if_empty()
+            .extend(segments);
+        for (name, value) in query.iter().filter_map(|(name, value)| Some((name, value.as_ref()?))) {
+            url.query_pairs_mut().append_pair(name, value);
+        }
+        Ok(url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client(base: &str) -> ApiClient {
+        ApiClient {
+            base: base.to_string(),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    #[test]
+    fn segments_are_joined_onto_the_origin() {
+        let url = client("http://127.0.0.1:8765")
+            .url(&["api", "tasks", "TASK-1"], &[("limit", Some("5".into())), ("status", None)])
+            .unwrap();
+        assert_eq!(url.as_str(), "http://127.0.0.1:8765/api/tasks/TASK-1?limit=5");
+    }
+
+    #[test]
+    fn segments_are_escaped() {
+        let url = client("http://host").url(&["api", "agents", "a/b", "c d"], &[]).unwrap();
+        assert_eq!(url.path(), "/api/agents/a%2Fb/c%20d");
+    }
+
+    #[test]
+    fn base_path_is_kept_before_the_segments() {
+        // Not an accepted backend URL, hence the doubled `api`
+        let url = client("https://host/api/").url(&["api", "tasks"], &[]).unwrap();
+        assert_eq!(url.path(), "/api/api/tasks");
+    }
+}
@@ -0,0 +1,316 @@
+use futures_util::{Sink, SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+use tokio::sync::Notify;
+use tokio_tungstenite::tungstenite::Message;
+use tracing::{debug, info, info_span, Instrument};
+
+use crate::notifications;
+use crate::profiles::Backend;
+use crate::settings;
+use crate::telemetry;
+
+/// Prefix of the Tauri events backend messages are re-emitted as, e.g. `orchestrator://task_update`
+const EVENT_PREFIX: &str = "orchestrator://";
+
+/// Subscription targets the backend accepts
+const TARGETS: &[&str] = &["task", "agent", "logs", "phase", "tmux"];
+
+/// Subscription ID matching every entity of a target
+const WILDCARD_ID: &str = "*";
+
+/// Targets `WILDCARD_ID` may be used with; following every log or tmux
+/// session at once is not supported
+const WILDCARD_TARGETS: &[&str] = &["task", "agent", "phase"];
+
+/// Delay before reconnecting to the backend's WebSocket
+const RECONNECT_DELAY: Duration = Duration::from_secs(2);
+
+/// Delay between `ping` messages; a connection silent for two of them is dropped
+const PING_INTERVAL: Duration = Duration::from_secs(20);
+
+/// Entities to receive events for, as sent in `subscribe` messages.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub(crate) struct Subscription {
+    pub target: String,
+    pub id: String,
+}
+
+impl Subscription {
+    fn new(target: &str, id: &str) -> Self {
+        Self {
+            target: target.to_string(),
+            id: id.to_string(),
+        }
+    }
+
+    fn matches(&self, key: &Subscription) -> bool {
+        self.target == key.target && (self.id == key.id || self.id == WILDCARD_ID)
+    }
+}
+
+/// `bridge-connection-changed` payload.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ConnectionChangedPayload {
+    profile: String,
+    connected: bool,
+}
+
+/// A profile's single connection to the backend's `/ws`, shared by its windows.
+///
+/// Windows subscribe here instead of on the socket; the connection sends the
+/// union of their subscriptions and re-sends it after reconnecting.
+#[derive(Default)]
+pub(crate) struct Bridge {
+    /// Window label -> what it subscribed to
+    windows: Mutex<HashMap<String, HashSet<Subscription>>>,
+    /// Signalled when the subscriptions change
+    changed: Notify,
+    connected: AtomicBool,
+}
+
+impl Bridge {
+    /// Subscribe the window labelled `label` to `subscription`.
+    pub(crate) fn subscribe(&self, label: &str, subscription: Subscription) -> Result<(), String> {
+        if !TARGETS.contains(&subscription.target.as_str()) {
+            return Err(format!(
+                "Invalid subscription target {:?}, expected one of {}",
+                subscription.target,
+                TARGETS.join(", ")
+            ));
+        }
+        if subscription.id.is_empty() {
+            return Err("Missing subscription ID".to_string());
+        }
+        if subscription.id == WILDCARD_ID && !WILDCARD_TARGETS.contains(&subscription.target.as_str()) {
+            return Err(format!(
+                "Cannot subscribe to every {} at once, {:?} is only allowed for {}",
+                subscription.target,
+                WILDCARD_ID,
+                WILDCARD_TARGETS.join(", ")
+            ));
+        }
+        let added = self
+            .windows
+            .lock()
+            .unwrap()
+            .entry(label.to_string())
+            .or_default()
+            .insert(subscription);
+        if added {
+            self.changed.notify_one();
+        }
+        Ok(())
+    }
+
+    pub(crate) fn unsubscribe(&self, label: &str, subscription: &Subscription) {
+        let removed = self
+            .windows
+            .lock()
+            .unwrap()
+            .get_mut(label)
+            .is_some_and(|subscriptions| subscriptions.remove(subscription));
+        if removed {
+            self.changed.notify_one();
+        }
+    }
+
+    /// Drop the subscriptions of a closed window or one switched to another profile.
+    pub(crate) fn forget_window(&self, label: &str) {
+        if self.windows.lock().unwrap().remove(label).is_some() {
+            self.changed.notify_one();
+        }
+    }
+
+    pub(crate) fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::SeqCst)
+    }
+
+    /// Everything the connection should be subscribed to: the windows'
+    /// subscriptions and those the notifier needs.
+    fn wanted(&self) -> HashSet<Subscription> {
+        let windows = self.windows.lock().unwrap();
+        notifications::SUBSCRIPTIONS
+            .iter()
+            .map(|target| Subscription::new(target, WILDCARD_ID))
+            .chain(windows.values().flatten().cloned())
+            .collect()
+    }
+
+    /// Windows subscribed to any of `keys`; all windows using the bridge if `keys` is empty.
+    fn listeners(&self, keys: &[Subscription]) -> Vec<String> {
+        self.windows
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, subscriptions)| {
+                keys.is_empty() || subscriptions.iter().any(|s| keys.iter().any(|key| s.matches(key)))
+            })
+            .map(|(label, _)| label.clone())
+            .collect()
+    }
+}
+
+/// Keep the profile's bridge connected until the profile is deactivated.
+/// Reconnects across backend restarts.
+pub(crate) fn run(app: AppHandle, backend: Arc<Backend>) {
+    let span = info_span!("bridge", profile = %backend.profile);
+    let task = async move {
+        loop {
+            tokio::select! {
+                result = connect(&app, &backend) => {
+                    set_connected(&app, &backend, false);
+                    if let Err(e) = result {
+                        debug!(error = %e, "event connection unavailable");
+                    }
+                }
+                _ = backend.sidecar.shut_down() => break,
+            }
+            tokio::select! {
+                _ = tokio::time::sleep(RECONNECT_DELAY) => {}
+                _ = backend.sidecar.shut_down() => break,
+            }
+        }
+        set_connected(&app, &backend, false);
+        debug!("event bridge stopped");
+    };
+    tauri::async_runtime::spawn(task.instrument(span));
+}
+
+async fn connect(app: &AppHandle, backend: &Backend) -> Result<(), String> {
+    let url = format!("{}/ws", settings::ws_url(&backend.url()));
+    let (socket, _) = tokio_tungstenite::connect_async(&url)
+        .await
+        .map_err(|e| format!("Cannot connect to {}: {}", url, e))?;
+    let (mut sink, mut stream) = socket.split();
+    info!(%url, "event bridge connected");
+    set_connected(app, backend, true);
+
+    let bridge = &backend.bridge;
+    let mut sent = HashSet::new();
+    let mut ping = tokio::time::interval(PING_INTERVAL);
+    let mut last_seen = Instant::now();
+    let mut result = sync(&mut sink, &mut sent, bridge.wanted()).await;
+    while result.is_ok() {
+        tokio::select! {
+            message = stream.next() => match message {
+                None | Some(Ok(Message::Close(_))) => break,
+                Some(Err(e)) => result = Err(e.to_string()),
+                Some(Ok(message)) => {
+                    last_seen = Instant::now();
+                    if let Message::Text(text) = message {
+                        dispatch(app, backend, &text);
+                    }
+                }
+            },
+            _ = bridge.changed.notified() => result = sync(&mut sink, &mut sent, bridge.wanted()).await,
+            _ = ping.tick() => {
+                result = if last_seen.elapsed() > PING_INTERVAL * 2 {
+                    Err("Backend stopped answering pings".to_string())
+                } else {
+                    send(&mut sink, serde_json::json!({ "type": "ping" })).await
+                };
+            }
+        }
+    }
+    result
+}
+
+/// Subscribe to what is `wanted` and not yet `sent`, and unsubscribe from the rest.
+async fn sync<S>(sink: &mut S, sent: &mut HashSet<Subscription>, wanted: HashSet<Subscription>) -> Result<(), String>
+where
+    S: Sink<Message> + Unpin,
+    S::Error: std::fmt::Display,
+{
+    for subscription in wanted.difference(sent) {
+        let message = serde_json::json!({ "type": "subscribe", "target": subscription.target, "id": subscription.id });
+        send(sink, message).await?;
+    }
+    for subscription in sent.difference(&wanted) {
+        let message = serde_json::json!({ "type": "unsubscribe", "target": subscription.target, "id": subscription.id });
+        send(sink, message).await?;
+    }
+    *sent = wanted;
+    Ok(())
+}
+
+async fn send<S>(sink: &mut S, message: serde_json::Value) -> Result<(), String>
+where
+    S: Sink<Message> + Unpin,
+    S::Error: std::fmt::Display,
+{
+    sink.send(Message::Text(message.to_string()))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+fn set_connected(app: &AppHandle, backend: &Backend, connected: bool) {
+    if backend.bridge.connected.swap(connected, Ordering::SeqCst) != connected {
+        telemetry::emit(
+            app,
+            "bridge-connection-changed",
+            ConnectionChangedPayload {
+                profile: backend.profile.clone(),
+                connected,
+            },
+        );
+    }
+}
+
+/// Hand a backend message to the notifier and re-emit it to the windows subscribed to it.
+fn dispatch(app: &AppHandle, backend: &Backend, text: &str) {
+    let Ok(message) = serde_json::from_str::<serde_json::Value>(text) else {
+        debug!(%text, "ignoring malformed message");
+        return;
+    };
+    let Some(kind) = message.get("type").and_then(|kind| kind.as_str()) else {
+        return;
+    };
+    if kind == "pong" {
+        return;
+    }
+    if !kind.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        debug!(kind, "ignoring message with unusable type");
+        return;
+    }
+
+    notifications::handle(app, backend, &message);
+
+    let event = format!("{}{}", EVENT_PREFIX, kind);
+    for label in backend.bridge.listeners(&routing_keys(kind, &message)) {
+        telemetry::emit_to(app, &label, &event, message.clone());
+    }
+}
+
+/// Subscriptions a message is delivered to, mirroring the backend's routing.
+/// Empty for messages meant for every client.
+fn routing_keys(kind: &str, message: &serde_json::Value) -> Vec<Subscription> {
+    let field = |name: &str| message.get(name).and_then(|value| value.as_str());
+    let keys = match kind {
+        "task_update" => vec![("task", field("task_id"))],
+        "agent_update" => vec![("agent", field("agent_id"))],
+        "phase_change" => vec![("phase", field("task_id"))],
+        "log_chunk" => vec![("logs", field("agent_id"))],
+        "tmux_output" => vec![("tmux", field("session"))],
+        "agent_progress" => vec![("task", field("task_id")), ("agent", field("agent_id"))],
+        "finding_reported" => vec![
+            ("agent", field("agent_id")),
+            ("task", message.pointer("/finding/task_id").and_then(|id| id.as_str())),
+        ],
+        "subscription_confirmed" | "unsubscription_confirmed" => {
+            return field("target")
+                .zip(field("id"))
+                .map(|(target, id)| vec![Subscription::new(target, id)])
+                .unwrap_or_default();
+        }
+        _ => vec![],
+    };
+    keys.into_iter()
+        .filter_map(|(target, id)| id.map(|id| Subscription::new(target, id)))
+        .collect()
+}
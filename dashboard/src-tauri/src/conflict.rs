@@ -0,0 +1,252 @@
+use serde::Serialize;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tokio::process::Command;
+use tracing::{info, instrument, warn};
+
+use crate::health::{local_url, probe_dashboard};
+use crate::ports;
+use crate::profiles::{Backend, Profiles};
+use crate::sidecar::{force_kill, forget_stale, spawned_by_us, terminate};
+use crate::telemetry;
+
+/// Environment variable selecting what to do with a stale sidecar
+pub(crate) const CONFLICT_POLICY_ENV: &str = "CLAUDE_ORCHESTRATOR_PORT_CONFLICT";
+
+/// How long a killed stale backend gets to release its port
+const RELEASE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// What to do when a sidecar left behind by an earlier run holds the backend
+/// port. Anything else, including a dashboard API the user started, is
+/// always avoided.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ConflictPolicy {
+    /// Use the running backend if it is healthy
+    Adopt,
+    /// Stop it and take the port over
+    Kill,
+    /// Leave it alone and use another port
+    Avoid,
+}
+
+impl ConflictPolicy {
+    pub(crate) fn from_env() -> Self {
+        match std::env::var(CONFLICT_POLICY_ENV).as_deref() {
+            Ok("adopt") => Self::Adopt,
+            Ok("kill") => Self::Kill,
+            Ok("avoid") | Err(_) => Self::Avoid,
+            Ok(other) => {
+                warn!(env = CONFLICT_POLICY_ENV, value = other, "ignoring invalid conflict policy, using avoid");
+                Self::Avoid
+            }
+        }
+    }
+}
+
+/// Who is listening on a port we wanted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum Occupant {
+    /// A sidecar this app spawned in an earlier run and never saw exit
+    StaleSidecar,
+    /// A dashboard API the app didn't spawn, e.g. a developer's `uvicorn`
+    DashboardApi,
+    /// Some other program
+    Foreign,
+}
+
+/// How the port was finally obtained.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PortResolution {
+    /// Spawn a new sidecar on this port
+    Spawn(u16),
+    /// Reuse the stale backend already listening on this port
+    Adopt { port: u16, pid: Option<u32> },
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PortConflictPayload {
+    profile: String,
+    port: u16,
+    occupant: Occupant,
+    pid: Option<u32>,
+    process_name: Option<String>,
+    version: Option<String>,
+    action: ConflictPolicy,
+    new_port: Option<u16>,
+}
+
+/// Make sure `wanted` can be used for the profile's backend, resolving any conflict.
+#[instrument(skip(app, backend))]
+pub(crate) async fn resolve_port(
+    app: &AppHandle,
+    backend: &Backend,
+    wanted: u16,
+    policy: ConflictPolicy,
+) -> Result<PortResolution, String> {
+    if ports::is_port_free(wanted) {
+        return Ok(PortResolution::Spawn(wanted));
+    }
+
+    let client = backend.probe.client();
+    let health = probe_dashboard(&client, &local_url(wanted)).await;
+    let pid = listener_pid(wanted).await;
+    let process_name = match pid {
+        Some(pid) => process_name(pid).await,
+        None => None,
+    };
+
+    let is_dashboard = health.is_some()
+        || process_name
+            .as_deref()
+            .is_some_and(|name| name.contains("dashboard-api"));
+    // Only a pid recorded when we spawned it proves the process is ours
+    let occupant = match pid {
+        Some(pid) if is_dashboard && spawned_by_us(app, pid) => Occupant::StaleSidecar,
+        _ if is_dashboard => Occupant::DashboardApi,
+        _ => Occupant::Foreign,
+    };
+    let version = health.as_ref().map(|h| h.version.clone());
+
+    info!(port = wanted, ?occupant, ?pid, ?process_name, ?version, "backend port is in use");
+
+    // Another profile's sidecar is never stale
+    let sibling = pid.is_some_and(|pid| app.state::<Profiles>().owns_pid(pid));
+    let mut action = match occupant {
+        Occupant::StaleSidecar if !sibling => policy,
+        _ => ConflictPolicy::Avoid,
+    };
+
+    // Only a healthy backend is worth adopting
+    if action == ConflictPolicy::Adopt && !health.as_ref().is_some_and(|h| h.is_ours()) {
+        info!(?version, "not adopting unhealthy sidecar, killing it instead");
+        action = ConflictPolicy::Kill;
+    }
+
+    if action == ConflictPolicy::Kill {
+        // Without a pid there is nothing to kill
+        let stopped = match pid {
+            Some(pid) => {
+                let stopped = stop_process(pid, wanted).await;
+                if stopped {
+                    forget_stale(app, pid);
+                }
+                stopped
+            }
+            None => false,
+        };
+        if !stopped {
+            warn!(port = wanted, "could not stop stale backend, avoiding it");
+            action = ConflictPolicy::Avoid;
+        }
+    }
+
+    let resolution = match action {
+        ConflictPolicy::Adopt => PortResolution::Adopt { port: wanted, pid },
+        ConflictPolicy::Kill => PortResolution::Spawn(wanted),
+        ConflictPolicy::Avoid => {
+            let port = ports::choose_port(&[backend.sidecar.preferred_port()])
+                .map_err(|e| format!("Failed to find a free port: {}", e))?;
+            PortResolution::Spawn(port)
+        }
+    };
+
+    let new_port = match resolution {
+        PortResolution::Spawn(port) if port != wanted => Some(port),
+        _ => None,
+    };
+
+    info!(?action, ?new_port, "resolved port conflict");
+    telemetry::emit(
+        app,
+        "backend-port-conflict",
+        PortConflictPayload {
+            profile: backend.profile.clone(),
+            port: wanted,
+            occupant,
+            pid,
+            process_name,
+            version,
+            action,
+            new_port,
+        },
+    );
+
+    Ok(resolution)
+}
+
+/// SIGTERM the process, falling back to SIGKILL, until `port` is released.
+pub(crate) async fn stop_process(pid: u32, port: u16) -> bool {
+    info!(pid, port, "stopping stale backend");
+    terminate(pid);
+    if wait_for_release(port, RELEASE_TIMEOUT).await {
+        return true;
+    }
+    force_kill(pid);
+    wait_for_release(port, RELEASE_TIMEOUT).await
+}
+
+async fn wait_for_release(port: u16, timeout: Duration) -> bool {
+    let deadline = tokio::time::Instant::now() + timeout;
+    while tokio::time::Instant::now() < deadline {
+        if ports::is_port_free(port) {
+            return true;
+        }
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+    ports::is_port_free(port)
+}
+
+/// Pid of the process listening on `port`, if it can be determined.
+#[cfg(unix)]
+async fn listener_pid(port: u16) -> Option<u32> {
+    let output = Command::new("lsof")
+        .args(["-nP", "-t", &format!("-iTCP:{}", port), "-sTCP:LISTEN"])
+        .output()
+        .await
+        .ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+#[cfg(windows)]
+async fn listener_pid(port: u16) -> Option<u32> {
+    let output = Command::new("netstat").args(["-ano", "-p", "TCP"]).output().await.ok()?;
+    let suffix = format!(":{}", port);
+    String::from_utf8_lossy(&output.stdout).lines().find_map(|line| {
+        let cols: Vec<&str> = line.split_whitespace().collect();
+        match cols.as_slice() {
+            [_, local, _, "LISTENING", pid] if local.ends_with(&suffix) => pid.parse().ok(),
+            _ => None,
+        }
+    })
+}
+
+#[cfg(unix)]
+async fn process_name(pid: u32) -> Option<String> {
+    let output = Command::new("ps")
+        .args(["-p", &pid.to_string(), "-o", "comm="])
+        .output()
+        .await
+        .ok()?;
+    let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!name.is_empty()).then_some(name)
+}
+
+#[cfg(windows)]
+async fn process_name(pid: u32) -> Option<String> {
+    let output = Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {}", pid), "/FO", "CSV", "/NH"])
+        .output()
+        .await
+        .ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let name = stdout.split(',').next()?.trim_matches('"').trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
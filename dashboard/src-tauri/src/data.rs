@@ -0,0 +1,161 @@
+use serde_json::Value;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::AppHandle;
+use tracing::debug;
+
+use crate::api::{AgentQuery, ApiClient, ApiError, FindingQuery, LogQuery, OutputQuery, TaskQuery};
+use crate::models::{
+    AgentData, AgentFinding, AgentOutput, AgentProgress, Handover, LogEntry, PhaseRecord, TaskDetail, TaskSummary,
+    TmuxSessionInfo,
+};
+use crate::offline::OfflineStore;
+use crate::profiles::Backend;
+use crate::state::BackendStatus;
+
+/// Where the data commands read from: the backend's REST API or, when the
+/// backend never became ready, the workspace's state database.
+pub(crate) enum DataSource {
+    Api(ApiClient),
+    Offline(OfflineStore),
+}
+
+impl DataSource {
+    pub(crate) fn for_backend(app: &AppHandle, backend: &Backend) -> Result<Self, ApiError> {
+        if !is_offline(backend) {
+            return Ok(Self::Api(ApiClient::new(backend)));
+        }
+        let bases = backend.workspace.bases(app);
+        if bases.is_empty() {
+            return Err(ApiError::Unavailable {
+                message: "The backend is not ready and no workspace is known".to_string(),
+            });
+        }
+        debug!(profile = %backend.profile, ?bases, "reading the state databases");
+        Ok(Self::Offline(OfflineStore::open(&bases)?))
+    }
+
+    /// `api` or `offline`, as reported by `get_data_source`.
+    pub(crate) fn kind(&self) -> &'static str {
+        match self {
+            Self::Api(_) => "api",
+            Self::Offline(_) => "offline",
+        }
+    }
+
+    pub(crate) async fn tasks(&self, query: &TaskQuery) -> Result<Vec<TaskSummary>, ApiError> {
+        match self {
+            Self::Api(api) => api.tasks(query).await,
+            Self::Offline(store) => store.tasks(query).await,
+        }
+    }
+
+    pub(crate) async fn task(&self, task_id: &str) -> Result<TaskDetail, ApiError> {
+        match self {
+            Self::Api(api) => api.task(task_id).await,
+            Self::Offline(store) => store.task(task_id).await,
+        }
+    }
+
+    pub(crate) async fn task_registry(&self, task_id: &str) -> Result<Value, ApiError> {
+        match self {
+            Self::Api(api) => api.task_registry(task_id).await,
+            Self::Offline(store) => store.task_registry(task_id).await,
+        }
+    }
+
+    pub(crate) async fn handovers(&self, task_id: &str) -> Result<Vec<Handover>, ApiError> {
+        match self {
+            Self::Api(api) => api.handovers(task_id).await,
+            Self::Offline(store) => store.handovers(task_id).await,
+        }
+    }
+
+    pub(crate) async fn agents(&self, task_id: &str, query: &AgentQuery) -> Result<Vec<AgentData>, ApiError> {
+        match self {
+            Self::Api(api) => api.agents(task_id, query).await,
+            Self::Offline(store) => store.agents(task_id, query).await,
+        }
+    }
+
+    pub(crate) async fn agent_progress(
+        &self,
+        task_id: &str,
+        agent_id: &str,
+        limit: Option<u32>,
+    ) -> Result<Vec<AgentProgress>, ApiError> {
+        match self {
+            Self::Api(api) => api.agent_progress(task_id, agent_id, limit).await,
+            Self::Offline(store) => store.agent_progress(task_id, agent_id).await,
+        }
+    }
+
+    pub(crate) async fn agent_findings(
+        &self,
+        task_id: &str,
+        agent_id: &str,
+        query: &FindingQuery,
+    ) -> Result<Vec<AgentFinding>, ApiError> {
+        match self {
+            Self::Api(api) => api.agent_findings(task_id, agent_id, query).await,
+            Self::Offline(store) => store.agent_findings(task_id, agent_id, query).await,
+        }
+    }
+
+    pub(crate) async fn agent_logs(
+        &self,
+        task_id: &str,
+        agent_id: &str,
+        query: &LogQuery,
+    ) -> Result<Vec<LogEntry>, ApiError> {
+        self.api("Agent logs")?.agent_logs(task_id, agent_id, query).await
+    }
+
+    pub(crate) async fn agent_output(
+        &self,
+        task_id: &str,
+        agent_id: &str,
+        query: &OutputQuery,
+    ) -> Result<AgentOutput, ApiError> {
+        self.api("Agent output")?.agent_output(task_id, agent_id, query).await
+    }
+
+    pub(crate) async fn phases(&self, task_id: &str) -> Result<Vec<PhaseRecord>, ApiError> {
+        match self {
+            Self::Api(api) => api.phases(task_id).await,
+            Self::Offline(store) => store.phases(task_id).await,
+        }
+    }
+
+    pub(crate) async fn tmux_sessions(&self) -> Result<Vec<TmuxSessionInfo>, ApiError> {
+        self.api("tmux sessions")?.tmux_sessions().await
+    }
+
+    /// The API client for data only the backend serves.
+    fn api(&self, what: &str) -> Result<&ApiClient, ApiError> {
+        match self {
+            Self::Api(api) => Ok(api),
+            Self::Offline(_) => Err(ApiError::Unavailable {
+                message: format!("{} need the backend, which is not running", what),
+            }),
+        }
+    }
+}
+
+/// Whether the backend is stopped, gave up, or has been starting for longer
+/// than its startup timeout without becoming ready.
+fn is_offline(backend: &Backend) -> bool {
+    let state = backend.state.snapshot();
+    match state.status {
+        BackendStatus::Stopped | BackendStatus::Failed => true,
+        BackendStatus::Ready | BackendStatus::Degraded if state.ready_at.is_some() => false,
+        _ => {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0);
+            state
+                .started_at
+                .is_some_and(|started| now.saturating_sub(started) > backend.probe.startup_timeout_secs * 1000)
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, Url};
+use tauri_plugin_deep_link::DeepLinkExt;
+use tracing::{info, warn};
+
+#[cfg(desktop)]
+use crate::instance;
+use crate::telemetry;
+
+/// URL scheme registered for the app
+pub(crate) const SCHEME: &str = "claude-orchestrator";
+
+/// Longest ID accepted in a link
+const MAX_ID_LEN: usize = 128;
+
+/// Where a `claude-orchestrator://` link points, emitted as `navigate`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub(crate) enum NavigateTarget {
+    /// `claude-orchestrator://task/<task_id>`
+    Task { task_id: String },
+    /// `claude-orchestrator://agent/<task_id>/<agent_id>`
+    Agent { task_id: String, agent_id: String },
+    /// `claude-orchestrator://review/<review_id>`
+    Review { review_id: String },
+}
+
+impl NavigateTarget {
+    /// Parse and validate a deep link.
+    pub(crate) fn parse(url: &Url) -> Result<Self, String> {
+        if url.scheme() != SCHEME {
+            return Err(format!("Not a {}:// link: {}", SCHEME, url));
+        }
+        let kind = url.host_str().unwrap_or_default();
+        let segments: Vec<&str> = url
+            .path_segments()
+            .map(|segments| segments.filter(|s| !s.is_empty()).collect())
+            .unwrap_or_default();
+
+        match (kind, segments.as_slice()) {
+            ("task", [task_id]) => Ok(Self::Task {
+                task_id: task_id_arg(task_id)?,
+            }),
+            ("agent", [task_id, agent_id]) => Ok(Self::Agent {
+                task_id: task_id_arg(task_id)?,
+                agent_id: id_arg("agent", agent_id)?,
+            }),
+            ("review", [review_id]) => Ok(Self::Review {
+                review_id: id_arg("review", review_id)?,
+            }),
+            _ => Err(format!("Unsupported link: {}", url)),
+        }
+    }
+}
+
+fn id_arg(what: &str, id: &str) -> Result<String, String> {
+    let valid = !id.is_empty()
+        && id.len() <= MAX_ID_LEN
+        && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(id.to_string())
+    } else {
+        Err(format!("Invalid {} ID {:?}", what, id))
+    }
+}
+
+fn task_id_arg(id: &str) -> Result<String, String> {
+    let id = id_arg("task", id)?;
+    if id.starts_with("TASK-") {
+        Ok(id)
+    } else {
+        Err(format!("Invalid task ID {:?}", id))
+    }
+}
+
+/// Link the app was launched with, kept until the frontend asks for it.
+#[derive(Default)]
+pub(crate) struct PendingNavigation(Mutex<Option<NavigateTarget>>);
+
+impl PendingNavigation {
+    pub(crate) fn take(&self) -> Option<NavigateTarget> {
+        self.0.lock().unwrap().take()
+    }
+
+    /// Keep `target` for the next page that asks, e.g. a window being opened.
+    pub(crate) fn set(&self, target: NavigateTarget) {
+        *self.0.lock().unwrap() = Some(target);
+    }
+}
+
+/// Register the scheme where that happens at runtime and start handling links.
+pub(crate) fn init(app: &AppHandle) {
+    app.manage(PendingNavigation::default());
+
+    // Bundles register through their desktop file / registry entry; this
+    // covers AppImages and development builds
+    #[cfg(any(target_os = "linux", all(debug_assertions, windows)))]
+    if let Err(e) = app.deep_link().register_all() {
+        warn!(error = %e, "failed to register {}:// links", SCHEME);
+    }
+
+    // The frontend isn't listening yet, so a launch link waits to be taken
+    if let Ok(Some(urls)) = app.deep_link().get_current() {
+        if let Some(target) = urls.iter().find_map(parse_logged) {
+            app.state::<PendingNavigation>().set(target);
+        }
+    }
+
+    let handle = app.clone();
+    app.deep_link().on_open_url(move |event| {
+        for url in event.urls() {
+            if let Some(target) = parse_logged(&url) {
+                #[cfg(desktop)]
+                instance::focus_main_window(&handle);
+                telemetry::emit(&handle, "navigate", target);
+            }
+        }
+    });
+}
+
+fn parse_logged(url: &Url) -> Option<NavigateTarget> {
+    match NavigateTarget::parse(url) {
+        Ok(target) => {
+            info!(%url, ?target, "opening link");
+            Some(target)
+        }
+        Err(e) => {
+            warn!(error = %e, "ignoring link");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(url: &str) -> Result<NavigateTarget, String> {
+        NavigateTarget::parse(&Url::parse(url).unwrap())
+    }
+
+    #[test]
+    fn task_link() {
+        assert_eq!(
+            parse("claude-orchestrator://task/TASK-20260104-150000-abc12345"),
+            Ok(NavigateTarget::Task {
+                task_id: "TASK-20260104-150000-abc12345".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn agent_link() {
+        assert_eq!(
+            parse("claude-orchestrator://agent/TASK-1/investigator-150100_a1"),
+            Ok(NavigateTarget::Agent {
+                task_id: "TASK-1".to_string(),
+                agent_id: "investigator-150100_a1".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn review_link() {
+        assert_eq!(
+            parse("claude-orchestrator://review/review-42"),
+            Ok(NavigateTarget::Review {
+                review_id: "review-42".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn trailing_slash_is_ignored() {
+        assert!(parse("claude-orchestrator://task/TASK-1/").is_ok());
+    }
+
+    #[test]
+    fn other_scheme_is_rejected() {
+        assert!(parse("https://task/TASK-1").is_err());
+    }
+
+    #[test]
+    fn unknown_kind_or_arity_is_rejected() {
+        assert!(parse("claude-orchestrator://settings/TASK-1").is_err());
+        assert!(parse("claude-orchestrator://task").is_err());
+        assert!(parse("claude-orchestrator://task/TASK-1/extra").is_err());
+        assert!(parse("claude-orchestrator://agent/TASK-1").is_err());
+    }
+
+    #[test]
+    fn task_id_needs_its_prefix() {
+        assert!(parse("claude-orchestrator://task/job-1").is_err());
+    }
+
+    #[test]
+    fn ids_are_validated() {
+        assert!(parse("claude-orchestrator://task/TASK-1%2F..").is_err());
+        assert!(parse("claude-orchestrator://agent/TASK-1/a%20b").is_err());
+        assert!(parse(&format!("claude-orchestrator://review/{}", "r".repeat(MAX_ID_LEN + 1))).is_err());
+        assert!(parse(&format!("claude-orchestrator://review/{}", "r".repeat(MAX_ID_LEN))).is_ok());
+    }
+}
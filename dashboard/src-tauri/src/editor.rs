@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tauri::AppHandle;
+use tauri_plugin_opener::OpenerExt;
+use tracing::info;
+
+use crate::launcher;
+
+/// Editors known without configuration: the program and its arguments,
+/// where `{path}` and `{line}` are replaced by the file and line to open.
+const EDITORS: &[(&str, &[&str])] = &[
+    ("code", &["code", "--goto", "{path}:{line}"]),
+    ("cursor", &["cursor", "--goto", "{path}:{line}"]),
+    ("zed", &["zed", "{path}:{line}"]),
+    ("subl", &["subl", "{path}:{line}"]),
+    ("idea", &["idea", "--line", "{line}", "{path}"]),
+];
+
+/// Editor `open_agent_file` opens files in, from the `editor` section of the
+/// settings file. Without either field files open in their default app.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct EditorConfig {
+    /// Preset to use: `code`, `cursor`, `zed`, `subl` or `idea`
+    pub name: Option<String>,
+    /// Program and arguments run instead of a preset, with `{path}` and
+    /// `{line}` replaced by the file and line to open
+    pub command: Vec<String>,
+}
+
+/// Open `path` at `line` in the configured editor, or in its default app
+/// through the opener when none is configured (the line is then ignored).
+pub(crate) fn open(app: &AppHandle, config: &EditorConfig, path: &Path, line: Option<u32>) -> Result<(), String> {
+    let Some(template) = command(config)? else {
+        app.opener()
+            .open_path(path.to_string_lossy(), None::<&str>)
+            .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        info!(path = %path.display(), "opened file in its default app");
+        return Ok(());
+    };
+
+    let path = path.to_string_lossy();
+    let line = line.unwrap_or(1).to_string();
+    let argv: Vec<String> = template
+        .iter()
+        .map(|arg| arg.replace("{path}", &path).replace("{line}", &line))
+        .collect();
+    launcher::spawn(app, &argv)?;
+    info!(%path, %line, "opened file in editor");
+    Ok(())
+}
+
+/// Command template of the configured editor, `None` if there is none.
+fn command(config: &EditorConfig) -> Result<Option<Vec<String>>, String> {
+    if !config.command.is_empty() {
+        return Ok(Some(config.command.clone()));
+    }
+    let Some(name) = config.name.as_deref() else {
+        return Ok(None);
+    };
+    let (_, args) = EDITORS
+        .iter()
+        .find(|(editor, _)| *editor == name)
+        .ok_or_else(|| format!("Unknown editor {:?}, set editor.command to use it", name))?;
+    Ok(Some(args.iter().map(|arg| arg.to_string()).collect()))
+}
@@ -0,0 +1,258 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tracing::{debug, info, instrument, warn};
+
+/// Service name the dashboard API reports on `/` and `/health`. Versions of
+/// the app and the backend move independently, so this identifies the backend
+const SERVICE_NAME: &str = "Claude Orchestrator Dashboard API";
+
+/// Base URL of a backend listening on a local port.
+pub(crate) fn local_url(port: u16) -> String {
+    format!("http://localhost:{}", port)
+}
+
+/// How the backend is probed, from the `probe` section of the settings file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct ProbeConfig {
+    /// Delay between the first startup probes
+    pub initial_interval_ms: u64,
+    /// Upper bound for the delay between startup probes
+    pub max_interval_ms: u64,
+    /// Growth factor applied to the delay after each failed startup probe
+    pub backoff_multiplier: f64,
+    /// Give up waiting for startup after this long
+    pub startup_timeout_secs: u64,
+    /// Timeout for a single HTTP probe
+    pub request_timeout_ms: u64,
+    /// Delay between liveness probes once the backend is up
+    pub liveness_interval_secs: u64,
+    /// Consecutive liveness failures before the backend counts as unhealthy
+    pub liveness_failure_threshold: u32,
+    /// Also require `/health/deep` (workspace and state database reachable)
+    pub deep_check: bool,
+}
+
+impl Default for ProbeConfig {
+    fn default() -> Self {
+        Self {
+            initial_interval_ms: 250,
+            max_interval_ms: 2_000,
+            backoff_multiplier: 1.5,
+            startup_timeout_secs: 60,
+            request_timeout_ms: 2_000,
+            liveness_interval_secs: 10,
+            liveness_failure_threshold: 3,
+            deep_check: false,
+        }
+    }
+}
+
+impl ProbeConfig {
+    pub(crate) fn request_timeout(&self) -> Duration {
+        Duration::from_millis(self.request_timeout_ms.max(1))
+    }
+
+    pub(crate) fn liveness_interval(&self) -> Duration {
+        Duration::from_secs(self.liveness_interval_secs.max(1))
+    }
+
+    fn next_interval(&self, current: Duration) -> Duration {
+        current
+            .mul_f64(self.backoff_multiplier.max(1.0))
+            .min(Duration::from_millis(self.max_interval_ms))
+    }
+
+    /// HTTP client with this config's per-request timeout.
+    pub(crate) fn client(&self) -> reqwest::Client {
+        reqwest::Client::builder()
+            .timeout(self.request_timeout())
+            .build()
+            .unwrap_or_default()
+    }
+}
+
+/// Body of the backend's `/health` response.
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct HealthInfo {
+    pub status: String,
+    #[serde(default)]
+    pub service: String,
+    pub version: String,
+}
+
+#[derive(Deserialize)]
+struct RootInfo {
+    message: String,
+}
+
+impl HealthInfo {
+    /// Healthy and reporting the dashboard API's service name.
+    pub(crate) fn is_ours(&self) -> bool {
+        self.status == "healthy" && self.service == SERVICE_NAME
+    }
+}
+
+/// Body of the backend's `/health/deep` response.
+#[derive(Debug, Deserialize)]
+struct DeepHealthInfo {
+    status: String,
+    #[serde(default)]
+    checks: BTreeMap<String, DeepCheck>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeepCheck {
+    ok: bool,
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+/// Fetch and parse `/health` from the backend at `base_url`.
+pub(crate) async fn probe_health(client: &reqwest::Client, base_url: &str) -> Result<HealthInfo, String> {
+    let url = format!("{}/health", base_url);
+    let response = client.get(&url).send().await.map_err(|e| e.to_string())?;
+
+    if !response.status().is_success() {
+        return Err(format!("status {}", response.status()));
+    }
+    response
+        .json::<HealthInfo>()
+        .await
+        .map_err(|e| format!("unexpected /health body: {}", e))
+}
+
+/// Ask the backend to verify it can reach the workspace and `registry/state.sqlite3`.
+async fn probe_deep(client: &reqwest::Client, base_url: &str) -> Result<(), String> {
+    let url = format!("{}/health/deep", base_url);
+    let response = client.get(&url).send().await.map_err(|e| e.to_string())?;
+    let status = response.status();
+
+    // 503 still carries the failed checks
+    let body = response
+        .json::<DeepHealthInfo>()
+        .await
+        .map_err(|e| format!("unexpected /health/deep body ({}): {}", status, e))?;
+
+    if status.is_success() && body.status == "healthy" {
+        return Ok(());
+    }
+
+    let failures: Vec<String> = body
+        .checks
+        .iter()
+        .filter(|(_, check)| !check.ok)
+        .map(|(name, check)| {
+            format!(
+                "{} {}: {}",
+                name,
+                check.path.as_deref().unwrap_or("?"),
+                check.error.as_deref().unwrap_or("unavailable")
+            )
+        })
+        .collect();
+
+    if failures.is_empty() {
+        Err(format!("deep health check failed ({})", status))
+    } else {
+        Err(format!("deep health check failed: {}", failures.join("; ")))
+    }
+}
+
+/// One full health check: `/health` must report the dashboard API, plus the
+/// deep check when enabled.
+pub(crate) async fn check_backend(
+    client: &reqwest::Client,
+    base_url: &str,
+    probe: &ProbeConfig,
+) -> Result<(), String> {
+    let health = probe_health(client, base_url).await?;
+    if !health.is_ours() {
+        return Err(format!(
+            "unexpected server: status {:?}, service {:?} (want {:?})",
+            health.status, health.service, SERVICE_NAME
+        ));
+    }
+    if probe.deep_check {
+        probe_deep(client, base_url).await?;
+    }
+    Ok(())
+}
+
+/// If a dashboard API answers at `base_url`, return its reported health.
+///
+/// Any server can answer 200 on `/health`, so the root endpoint's service
+/// name is checked as well.
+pub(crate) async fn probe_dashboard(client: &reqwest::Client, base_url: &str) -> Option<HealthInfo> {
+    let url = format!("{}/", base_url);
+    let root = client
+        .get(&url)
+        .send()
+        .await
+        .ok()?
+        .json::<RootInfo>()
+        .await
+        .ok()?;
+
+    if root.message != SERVICE_NAME {
+        return None;
+    }
+    probe_health(client, base_url).await.ok()
+}
+
+/// Probe with backoff until the backend passes a health check.
+///
+/// Returns the last error once `startup_timeout_secs` has elapsed.
+#[instrument(skip(probe), fields(timeout_secs = probe.startup_timeout_secs))]
+pub(crate) async fn wait_for_backend_ready(base_url: &str, probe: &ProbeConfig) -> Result<(), String> {
+    let client = probe.client();
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(probe.startup_timeout_secs);
+    let mut interval = Duration::from_millis(probe.initial_interval_ms.max(50));
+    let mut attempt = 0u32;
+
+    loop {
+        attempt += 1;
+        let error = match check_backend(&client, base_url, probe).await {
+            Ok(()) => {
+                info!(attempt, "backend ready");
+                return Ok(());
+            }
+            Err(e) => e,
+        };
+
+        if tokio::time::Instant::now() + interval >= deadline {
+            warn!(attempt, %error, "backend failed to start");
+            return Err(error);
+        }
+        debug!(attempt, %error, next_ms = interval.as_millis() as u64, "readiness probe failed");
+
+        tokio::time::sleep(interval).await;
+        interval = probe.next_interval(interval);
+    }
+}
+
+/// Poll a backend we don't own until it stops passing health checks.
+///
+/// Returns the error from the last failed probe.
+#[instrument(skip(probe))]
+pub(crate) async fn wait_until_unhealthy(base_url: &str, probe: &ProbeConfig) -> String {
+    let client = probe.client();
+    let mut failures = 0;
+
+    loop {
+        tokio::time::sleep(probe.liveness_interval()).await;
+        match check_backend(&client, base_url, probe).await {
+            Ok(()) => failures = 0,
+            Err(e) => {
+                failures += 1;
+                debug!(failures, error = %e, "liveness probe failed");
+                if failures >= probe.liveness_failure_threshold.max(1) {
+                    return e;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use serde::Serialize;
+use std::path::Path;
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+
+use crate::telemetry;
+
+/// Label of the window focused when the app is launched again
+pub(crate) const MAIN_WINDOW: &str = "main";
+
+/// Prefix of orchestrator task IDs, e.g. `TASK-20240101-abcdef`
+const TASK_PREFIX: &str = "TASK-";
+
+/// `second-instance` payload: what a second launch was asked to open.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LaunchArgs {
+    /// Arguments of the second launch, without the executable
+    pub args: Vec<String>,
+    /// Working directory of the second launch
+    pub cwd: String,
+    /// First argument that looks like a task ID
+    pub task_id: Option<String>,
+    /// First argument naming an existing directory, made absolute
+    pub workspace: Option<String>,
+}
+
+impl LaunchArgs {
+    fn parse(argv: Vec<String>, cwd: String) -> Self {
+        let args: Vec<String> = argv.into_iter().skip(1).collect();
+        let task_id = args
+            .iter()
+            .find(|arg| arg.starts_with(TASK_PREFIX))
+            .cloned();
+        let workspace = args
+            .iter()
+            .filter(|arg| !arg.starts_with('-') && !arg.contains("://"))
+            .map(|arg| Path::new(&cwd).join(arg))
+            .find(|path| path.is_dir())
+            .map(|path| path.display().to_string());
+
+        Self {
+            args,
+            cwd,
+            task_id,
+            workspace,
+        }
+    }
+}
+
+/// Called in the running instance when the app is launched again: focus the
+/// main window and forward the new launch's arguments as `second-instance`.
+pub(crate) fn on_second_instance(app: &AppHandle, argv: Vec<String>, cwd: String) {
+    let launch = LaunchArgs::parse(argv, cwd);
+    info!(args = ?launch.args, task_id = ?launch.task_id, workspace = ?launch.workspace, "app launched again");
+
+    focus_main_window(app);
+    telemetry::emit(app, "second-instance", launch);
+}
+
+/// Bring the main window to the front, restoring it if minimized or hidden.
+pub(crate) fn focus_main_window(app: &AppHandle) {
+    match app.get_webview_window(MAIN_WINDOW) {
+        Some(window) => {
+            let _ = window.unminimize();
+            let _ = window.show();
+            if let Err(e) = window.set_focus() {
+                warn!(error = %e, "failed to focus main window");
+            }
+        }
+        None => warn!("main window is gone, cannot focus it"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argv(args: &[&str]) -> Vec<String> {
+        std::iter::once("claude-orchestrator").chain(args.iter().copied()).map(str::to_string).collect()
+    }
+
+    #[test]
+    fn executable_is_dropped() {
+        let launch = LaunchArgs::parse(argv(&[]), "/".to_string());
+        assert!(launch.args.is_empty());
+        assert_eq!((launch.task_id, launch.workspace), (None, None));
+    }
+
+    #[test]
+    fn first_task_id_wins() {
+        let launch = LaunchArgs::parse(argv(&["--verbose", "TASK-1", "TASK-2"]), "/".to_string());
+        assert_eq!(launch.args, ["--verbose", "TASK-1", "TASK-2"]);
+        assert_eq!(launch.task_id.as_deref(), Some("TASK-1"));
+    }
+
+    #[test]
+    fn workspace_is_resolved_against_cwd() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("project")).unwrap();
+        let cwd = dir.path().display().to_string();
+
+        let launch = LaunchArgs::parse(argv(&["missing", "project"]), cwd.clone());
+        assert_eq!(launch.workspace, Some(dir.path().join("project").display().to_string()));
+        assert_eq!(launch.cwd, cwd);
+
+        let absolute = dir.path().join("project").display().to_string();
+        let launch = LaunchArgs::parse(argv(&[&absolute]), "/".to_string());
+        assert_eq!(launch.workspace, Some(absolute));
+    }
+
+    #[test]
+    fn flags_and_urls_are_not_workspaces() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("--profile")).unwrap();
+        std::fs::create_dir_all(dir.path().join("claude-orchestrator:").join("task")).unwrap();
+        let launch = LaunchArgs::parse(
+            argv(&["--profile", "claude-orchestrator://task"]),
+            dir.path().display().to_string(),
+        );
+        assert_eq!(launch.workspace, None);
+    }
+}
@@ -0,0 +1,268 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tauri::AppHandle;
+use tauri_plugin_shell::process::CommandEvent;
+use tauri_plugin_shell::ShellExt;
+use tracing::{debug, info, warn};
+
+/// Terminal emulators `open_session` can launch, by name: the program and
+/// the arguments placed before the command it runs. Without a configured
+/// choice, the first one installed is used.
+///
+/// These programs are the launcher's allowlist: a configured `command` must
+/// run one of them. The capability file can't express this, as its shell
+/// scopes only apply to commands the webview invokes, not to processes the
+/// core spawns, and allowing the terminals there would let the page run them.
+#[cfg(target_os = "macos")]
+const PRESETS: &[(&str, &str, &[&str])] = &[
+    ("terminal", "osascript", &[]),
+    ("iterm", "osascript", &[]),
+    ("kitty", "kitty", &[]),
+    ("wezterm", "wezterm", &["start", "--"]),
+    ("alacritty", "alacritty", &["-e"]),
+];
+
+/// tmux only runs inside WSL on Windows, so sessions are attached there.
+#[cfg(windows)]
+const PRESETS: &[(&str, &str, &[&str])] = &[
+    ("wt", "wt.exe", &["wsl.exe", "--"]),
+    ("wsl", "wsl.exe", &["--"]),
+];
+
+#[cfg(not(any(target_os = "macos", windows)))]
+const PRESETS: &[(&str, &str, &[&str])] = &[
+    ("x-terminal-emulator", "x-terminal-emulator", &["-e"]),
+    ("gnome-terminal", "gnome-terminal", &["--"]),
+    ("konsole", "konsole", &["-e"]),
+    ("xfce4-terminal", "xfce4-terminal", &["-x"]),
+    ("kitty", "kitty", &[]),
+    ("wezterm", "wezterm", &["start", "--"]),
+    ("alacritty", "alacritty", &["-e"]),
+    ("foot", "foot", &[]),
+    ("xterm", "xterm", &["-e"]),
+];
+
+/// How `open_agent_terminal` launches a terminal, from the `externalTerminal`
+/// section of the settings file.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct ExternalTerminalConfig {
+    /// Preset to use, e.g. `kitty`; `None` picks the first one installed
+    pub terminal: Option<String>,
+    /// Program and arguments run instead of a preset. The program must be one
+    /// of the presets'; `{session}` is replaced by the session name and
+    /// `{command}` by the whole `tmux attach` command
+    pub command: Vec<String>,
+}
+
+/// Open tmux `session` in the user's terminal emulator. The terminal runs on
+/// its own; closing it only detaches from the session.
+pub(crate) fn open_session(app: &AppHandle, config: &ExternalTerminalConfig, session: &str) -> Result<(), String> {
+    validate_session(session)?;
+    spawn(app, &command_line(config, session)?)?;
+    info!(session, "opened session in terminal emulator");
+    Ok(())
+}
+
+/// Run `argv` detached from the app, logging how it exits.
+pub(crate) fn spawn(app: &AppHandle, argv: &[String]) -> Result<(), String> {
+    let (program, args) = argv.split_first().ok_or("The command is empty")?;
+    let (mut rx, _child) = app
+        .shell()
+        .command(program)
+        .args(args)
+        .spawn()
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+    let program = program.clone();
+    // Draining the output keeps the program from blocking on a full pipe
+    tauri::async_runtime::spawn(async move {
+        while let Some(event) = rx.recv().await {
+            match event {
+                CommandEvent::Terminated(status) if status.code != Some(0) => {
+                    warn!(%program, code = ?status.code, signal = ?status.signal, "launched program failed")
+                }
+                CommandEvent::Terminated(status) => debug!(%program, code = ?status.code, "launched program exited"),
+                CommandEvent::Error(e) => warn!(%program, error = %e, "failed to read launched program"),
+                _ => {}
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Program and arguments attaching to `session`, from the configured command
+/// or a preset.
+fn command_line(config: &ExternalTerminalConfig, session: &str) -> Result<Vec<String>, String> {
+    // `=` matches the name exactly rather than as a prefix
+    let attach = ["tmux", "attach-session", "-t", &format!("={}", session)].map(str::to_string);
+    if !config.command.is_empty() {
+        validate_command(&config.command)?;
+        let command = attach.join(" ");
+        return Ok(config
+            .command
+            .iter()
+            .map(|arg| arg.replace("{session}", session).replace("{command}", &command))
+            .collect());
+    }
+
+    let (name, program, prefix) = match config.terminal.as_deref() {
+        Some(terminal) => PRESETS
+            .iter()
+            .find(|(name, _, _)| *name == terminal)
+            .copied()
+            .ok_or_else(|| format!("Unknown terminal {:?}, expected one of {}", terminal, preset_names()))?,
+        None => PRESETS
+            .iter()
+            .find(|(_, program, _)| is_installed(program))
+            .copied()
+            .ok_or_else(|| format!("No terminal emulator found, install one of {}", preset_names()))?,
+    };
+    if program == "osascript" {
+        return Ok(apple_script(name, &attach.join(" ")));
+    }
+    Ok(std::iter::once(program.to_string())
+        .chain(prefix.iter().map(|arg| arg.to_string()))
+        .chain(attach)
+        .collect())
+}
+
+/// A configured command must run a preset's program, looked up on the `PATH`
+/// like the presets, and pass it the session.
+fn validate_command(command: &[String]) -> Result<(), String> {
+    let programs: Vec<&str> = PRESETS
+        .iter()
+        .map(|(_, program, _)| *program)
+        .filter(|program| *program != "osascript")
+        .collect();
+    let program = command[0].trim_end_matches(".exe");
+    if !programs.iter().any(|allowed| allowed.trim_end_matches(".exe") == program) {
+        return Err(format!(
+            "externalTerminal.command must run one of {}, not {:?}",
+            programs.join(", "),
+            command[0]
+        ));
+    }
+    if !command[1..].iter().any(|arg| arg.contains("{session}") || arg.contains("{command}")) {
+        return Err("externalTerminal.command must contain {session} or {command}".to_string());
+    }
+    Ok(())
+}
+
+fn preset_names() -> String {
+    PRESETS.iter().map(|(name, _, _)| *name).collect::<Vec<_>>().join(", ")
+}
+
+/// `osascript` invocation opening `command` in Terminal.app or iTerm2.
+/// Session names are validated, so `command` needs no quoting.
+fn apple_script(terminal: &str, command: &str) -> Vec<String> {
+    let script = match terminal {
+        "iterm" => format!(
+            "tell application \"iTerm\" to create window with default profile command \"{}\"",
+            command
+        ),
+        _ => format!(
+            "tell application \"Terminal\"\nactivate\ndo script \"{}\"\nend tell",
+            command
+        ),
+    };
+    vec!["osascript".to_string(), "-e".to_string(), script]
+}
+
+/// Whether `program` is on the `PATH`.
+fn is_installed(program: &str) -> bool {
+    std::env::var_os("PATH").is_some_and(|path| {
+        std::env::split_paths(&path).any(|dir| is_executable(&dir.join(program)))
+    })
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .is_ok_and(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.with_extension("exe").is_file()
+}
+
+/// Only agent sessions can be opened, as in the backend's tmux routes.
+pub(crate) fn validate_session(session: &str) -> Result<(), String> {
+    let valid = session.strip_prefix("agent_").is_some_and(|rest| {
+        !rest.is_empty() && rest.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    });
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("Invalid session name {:?}, expected agent_<id>", session))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn agent_sessions_are_valid() {
+        assert!(validate_session("agent_investigator-150100_a1").is_ok());
+        assert!(validate_session("agent_X").is_ok());
+    }
+
+    fn template(args: &[&str]) -> ExternalTerminalConfig {
+        ExternalTerminalConfig {
+            terminal: None,
+            command: args.iter().map(|arg| arg.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn command_template_is_substituted() {
+        let (_, program, _) = PRESETS.iter().find(|(_, program, _)| *program != "osascript").unwrap();
+        let config = template(&[program, "--title", "{session}", "-e", "sh", "-c", "{command}"]);
+        assert_eq!(
+            command_line(&config, "agent_a1").unwrap(),
+            [*program, "--title", "agent_a1", "-e", "sh", "-c", "tmux attach-session -t =agent_a1"]
+        );
+    }
+
+    #[test]
+    fn command_template_must_run_a_preset_program() {
+        let (_, program, _) = PRESETS.iter().find(|(_, program, _)| *program != "osascript").unwrap();
+        let path = format!("/tmp/{}", program);
+        for args in [
+            &["sh", "-c", "{command}"][..],
+            &[path.as_str(), "{command}"],
+            &["osascript", "-e", "{command}"],
+            &[program, "--hold"],
+        ] {
+            assert!(command_line(&template(args), "agent_a1").is_err(), "{:?} should be rejected", args);
+        }
+    }
+
+    #[test]
+    fn preset_is_used_by_name() {
+        let (name, program, prefix) = PRESETS.iter().find(|(_, program, _)| *program != "osascript").unwrap();
+        let config = ExternalTerminalConfig {
+            terminal: Some(name.to_string()),
+            command: Vec::new(),
+        };
+        let argv = command_line(&config, "agent_a1").unwrap();
+        assert_eq!(argv[0], *program);
+        assert_eq!(argv[1..=prefix.len()], prefix.iter().map(|arg| arg.to_string()).collect::<Vec<_>>()[..]);
+        assert_eq!(argv[prefix.len() + 1..], ["tmux", "attach-session", "-t", "=agent_a1"]);
+
+        let config = ExternalTerminalConfig {
+            terminal: Some("nope".to_string()),
+            command: Vec::new(),
+        };
+        assert!(command_line(&config, "agent_a1").is_err());
+    }
+
+    #[test]
+    fn other_sessions_are_rejected() {
+        for session in ["", "agent_", "main", "agent_a;rm -rf ~", "agent_a b", "agent_a:0", "agent_$(id)", "agent_é"] {
+            assert!(validate_session(session).is_err(), "{:?} should be rejected", session);
+        }
+    }
+}
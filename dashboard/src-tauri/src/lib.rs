@@ -1,162 +1,777 @@
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::time::Duration;
-use tauri::{AppHandle, Emitter};
-use tauri_plugin_shell::ShellExt;
-use tauri_plugin_shell::process::CommandEvent;
+mod api;
+mod bridge;
+mod conflict;
+mod data;
+mod deeplink;
+mod editor;
+mod health;
+#[cfg(desktop)]
+mod instance;
+mod launcher;
+mod logfile;
+mod logs;
+mod models;
+mod notifications;
+mod offline;
+mod ports;
+mod profiles;
+mod reveal;
+mod search;
+mod settings;
+mod sidecar;
+mod state;
+mod supervisor;
+mod tail;
+mod telemetry;
+#[cfg(desktop)]
+mod terminal;
+#[cfg(desktop)]
+mod tray;
+#[cfg(desktop)]
+mod watcher;
+mod workspace;
 
-// Track if backend is ready
-static BACKEND_READY: AtomicBool = AtomicBool::new(false);
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::ipc::{Channel, InvokeResponseBody};
+use tauri::{AppHandle, Manager, RunEvent, State, WebviewWindow, WindowEvent};
+use tauri_plugin_opener::OpenerExt;
+use tracing::{error, info, instrument, warn};
 
-// Backend port - using 8765 to avoid conflicts with dev server on 8000
-const BACKEND_PORT: u16 = 8765;
+use api::{AgentQuery, ApiError, FindingQuery, LogQuery, OutputQuery, TaskQuery};
+use bridge::Subscription;
+use data::DataSource;
+use logs::{LogLevel, LogLine};
+use models::{
+    AgentData, AgentFinding, AgentOutput, AgentProgress, Handover, LogEntry, PhaseRecord, TaskDetail, TaskSummary,
+    TmuxSessionInfo,
+};
+use notifications::{NotificationConfig, Notifications};
+use profiles::{Backend, ProfileInfo, Profiles};
+use reveal::OpenIn;
+use search::{SearchFilters, SearchHit};
+use settings::{BackendMode, Settings};
+use state::{BackendState, BackendStatus};
+use tail::{LogTails, TailBatch, TailOptions};
+#[cfg(desktop)]
+use terminal::{TerminalMode, Terminals};
+use workspace::AgentFile;
 
+/// Backend URL for the profile shown in the calling window.
 #[tauri::command]
-fn get_backend_url() -> String {
-    format!("http://localhost:{}", BACKEND_PORT)
+#[instrument(skip(window, profiles), fields(window = %window.label()))]
+fn get_backend_url(window: WebviewWindow, profiles: State<'_, Profiles>) -> Result<String, String> {
+    Ok(profiles.for_window(window.label())?.url())
 }
 
 #[tauri::command]
-fn get_ws_url() -> String {
-    format!("ws://localhost:{}", BACKEND_PORT)
+#[instrument(skip(window, profiles), fields(window = %window.label()))]
+fn get_ws_url(window: WebviewWindow, profiles: State<'_, Profiles>) -> Result<String, String> {
+    Ok(settings::ws_url(&profiles.for_window(window.label())?.url()))
 }
 
 #[tauri::command]
-fn is_backend_ready() -> bool {
-    BACKEND_READY.load(Ordering::SeqCst)
+#[instrument(skip(window, profiles), fields(window = %window.label()))]
+fn is_backend_ready(window: WebviewWindow, profiles: State<'_, Profiles>) -> bool {
+    profiles
+        .for_window(window.label())
+        .is_ok_and(|backend| backend.state.snapshot().status == BackendStatus::Ready)
 }
 
-async fn wait_for_backend_ready() -> bool {
-    let client = reqwest::Client::new();
-    let health_url = format!("http://localhost:{}/health", BACKEND_PORT);
+#[tauri::command]
+#[instrument(skip(window, profiles), fields(window = %window.label()))]
+fn get_backend_state(window: WebviewWindow, profiles: State<'_, Profiles>) -> Result<BackendState, String> {
+    Ok(profiles.for_window(window.label())?.state.snapshot())
+}
 
-    for attempt in 1..=30 {
-        match client.get(&health_url).timeout(Duration::from_secs(2)).send().await {
-            Ok(response) if response.status().is_success() => {
-                println!("[Tauri] Backend ready after {} attempts", attempt);
-                return true;
-            }
-            Ok(response) => {
-                println!("[Tauri] Backend returned status: {}", response.status());
-            }
-            Err(e) => {
-                if attempt % 5 == 0 {
-                    println!("[Tauri] Waiting for backend... attempt {}/30 - {}", attempt, e);
-                }
-            }
-        }
-        tokio::time::sleep(Duration::from_millis(500)).await;
+/// Buffered sidecar output after sequence number `since`, at or above `level`.
+#[tauri::command]
+#[instrument(skip(window, profiles), fields(window = %window.label()))]
+fn get_backend_logs(
+    window: WebviewWindow,
+    profiles: State<'_, Profiles>,
+    since: Option<u64>,
+    level: Option<LogLevel>,
+) -> Result<Vec<LogLine>, String> {
+    Ok(profiles.for_window(window.label())?.logs.query(since, level))
+}
+
+/// Target of the `claude-orchestrator://` link the app was launched with, once.
+#[tauri::command]
+#[instrument(skip(window, pending), fields(window = %window.label()))]
+fn take_pending_navigation(
+    window: WebviewWindow,
+    pending: State<'_, deeplink::PendingNavigation>,
+) -> Option<deeplink::NavigateTarget> {
+    pending.take()
+}
+
+/// Configured profiles, with the state of active ones.
+#[tauri::command]
+#[instrument(skip(window, profiles), fields(window = %window.label()))]
+fn list_profiles(window: WebviewWindow, profiles: State<'_, Profiles>) -> Vec<ProfileInfo> {
+    profiles.list(window.label())
+}
+
+/// Name of the profile shown in the calling window.
+#[tauri::command]
+#[instrument(skip(window, profiles), fields(window = %window.label()))]
+fn get_profile(window: WebviewWindow, profiles: State<'_, Profiles>) -> String {
+    profiles.window_profile(window.label())
+}
+
+/// Start the backend of profile `name` alongside the others.
+#[tauri::command]
+#[instrument(skip(app, window, profiles), fields(window = %window.label()))]
+fn activate_profile(
+    app: AppHandle,
+    window: WebviewWindow,
+    profiles: State<'_, Profiles>,
+    name: String,
+) -> Result<BackendState, String> {
+    Ok(profiles.activate(&app, &name)?.state.snapshot())
+}
+
+/// Stop the backend of profile `name`.
+#[tauri::command]
+#[instrument(skip(app, window), fields(window = %window.label()))]
+async fn deactivate_profile(app: AppHandle, window: WebviewWindow, name: String) -> Result<(), String> {
+    app.state::<Profiles>().deactivate(&app, &name).await
+}
+
+/// Show profile `name` in the calling window and open it by default from now on.
+///
+/// The window receives `profile-changed` with the profile's backend URL.
+#[tauri::command]
+#[instrument(skip(app, window, profiles), fields(window = %window.label()))]
+fn switch_profile(
+    app: AppHandle,
+    window: WebviewWindow,
+    profiles: State<'_, Profiles>,
+    name: String,
+) -> Result<BackendState, String> {
+    let backend = profiles.switch_window(&app, window.label(), &name)?;
+    profiles.set_default(&app, &name)?;
+    Ok(backend.state.snapshot())
+}
+
+/// Open a window showing profile `name`, or focus the one already open.
+#[tauri::command]
+#[instrument(skip(app, window, profiles), fields(window = %window.label()))]
+fn open_profile_window(
+    app: AppHandle,
+    window: WebviewWindow,
+    profiles: State<'_, Profiles>,
+    name: String,
+) -> Result<(), String> {
+    profiles.open_window(&app, &name)
+}
+
+/// Workspace base the calling window's sidecar runs against, if one was chosen or inherited.
+#[tauri::command]
+#[instrument(skip(window, profiles), fields(window = %window.label()))]
+fn get_workspace(window: WebviewWindow, profiles: State<'_, Profiles>) -> Result<Option<String>, String> {
+    let backend = profiles.for_window(window.label())?;
+    Ok(backend.workspace.get().map(|base| base.display().to_string()))
+}
+
+/// Switch the calling window's sidecar to the workspace at `path`, persist it
+/// and restart the backend.
+///
+/// Returns the validated workspace base.
+#[tauri::command]
+#[instrument(skip(app, window), fields(window = %window.label()))]
+async fn set_workspace(app: AppHandle, window: WebviewWindow, path: String) -> Result<String, String> {
+    let backend = app.state::<Profiles>().for_window(window.label())?;
+    apply_workspace(&app, backend, Path::new(&path)).await
+}
+
+/// Choose the calling window's workspace with the native folder picker and switch to it.
+///
+/// Returns `None` if the picker was cancelled.
+#[tauri::command]
+#[instrument(skip(app, window), fields(window = %window.label()))]
+async fn pick_workspace(app: AppHandle, window: WebviewWindow) -> Result<Option<String>, String> {
+    let backend = app.state::<Profiles>().for_window(window.label())?;
+    let current = backend.workspace.get();
+    match workspace::pick(&app, current.as_deref()).await {
+        Some(path) => apply_workspace(&app, backend, &path).await.map(Some),
+        None => Ok(None),
     }
+}
+
+async fn apply_workspace(app: &AppHandle, backend: Arc<Backend>, path: &Path) -> Result<String, String> {
+    if let BackendMode::External(url) = &backend.mode {
+        return Err(format!("The workspace of the external backend at {} is managed by that backend", url));
+    }
+    let base = workspace::validate(path)?;
+    app.state::<Profiles>().save_workspace(app, &backend.profile, &base)?;
+
+    backend.workspace.set(base.clone());
+    info!(profile = %backend.profile, workspace = %base.display(), "switching workspace, restarting backend");
+    supervisor::restart(app, backend).await;
+    Ok(base.display().to_string())
+}
 
-    println!("[Tauri] Backend failed to start after 30 attempts");
-    false
+/// Current log filter directive of the desktop core.
+#[tauri::command]
+#[instrument(skip(window), fields(window = %window.label()))]
+fn get_log_level(window: WebviewWindow) -> Option<String> {
+    telemetry::level()
 }
 
-fn spawn_backend(app: &AppHandle) -> Result<(), String> {
-    let shell = app.shell();
+/// Change the log filter at runtime, e.g. `debug` or `info,claude_orchestrator_dashboard_lib::health=trace`.
+#[tauri::command]
+#[instrument(skip(window), fields(window = %window.label()))]
+fn set_log_level(window: WebviewWindow, level: String) -> Result<(), String> {
+    telemetry::set_level(&level)?;
+    info!(%level, "log level changed");
+    Ok(())
+}
 
-    // Create sidecar command - handle errors gracefully
-    let sidecar_cmd = match shell.sidecar("dashboard-api") {
-        Ok(cmd) => cmd,
-        Err(e) => {
-            eprintln!("[Tauri] Failed to create sidecar command: {}", e);
-            return Err(format!("Failed to create sidecar: {}", e));
-        }
-    };
+/// Receive backend events for `target`/`id` (`*` for every entity) in this
+/// window, as `orchestrator://<type>` events.
+#[tauri::command]
+#[instrument(skip(window, profiles), fields(window = %window.label()))]
+fn ws_subscribe(window: WebviewWindow, profiles: State<'_, Profiles>, target: String, id: String) -> Result<(), String> {
+    profiles
+        .for_window(window.label())?
+        .bridge
+        .subscribe(window.label(), Subscription { target, id })
+}
 
-    // Spawn with port argument
-    let (mut rx, _child) = match sidecar_cmd
-        .args(["--port", &BACKEND_PORT.to_string()])
-        .spawn()
-    {
-        Ok(result) => result,
-        Err(e) => {
-            eprintln!("[Tauri] Failed to spawn sidecar: {}", e);
-            return Err(format!("Failed to spawn sidecar: {}", e));
-        }
-    };
+#[tauri::command]
+#[instrument(skip(window, profiles), fields(window = %window.label()))]
+fn ws_unsubscribe(window: WebviewWindow, profiles: State<'_, Profiles>, target: String, id: String) -> Result<(), String> {
+    profiles
+        .for_window(window.label())?
+        .bridge
+        .unsubscribe(window.label(), &Subscription { target, id });
+    Ok(())
+}
 
-    println!("[Tauri] Spawned dashboard-api sidecar on port {}", BACKEND_PORT);
-
-    // Monitor sidecar output in background
-    let app_handle = app.clone();
-    tauri::async_runtime::spawn(async move {
-        while let Some(event) = rx.recv().await {
-            match event {
-                CommandEvent::Stdout(line) => {
-                    let line_str = String::from_utf8_lossy(&line);
-                    println!("[API] {}", line_str);
-
-                    // Detect when uvicorn is ready
-                    if line_str.contains("Uvicorn running") || line_str.contains("Application startup complete") {
-                        BACKEND_READY.store(true, Ordering::SeqCst);
-                        let _ = app_handle.emit("backend-ready", true);
-                    }
-                }
-                CommandEvent::Stderr(line) => {
-                    let line_str = String::from_utf8_lossy(&line);
-                    eprintln!("[API ERR] {}", line_str);
-
-                    // Uvicorn logs to stderr
-                    if line_str.contains("Uvicorn running") || line_str.contains("Application startup complete") {
-                        BACKEND_READY.store(true, Ordering::SeqCst);
-                        let _ = app_handle.emit("backend-ready", true);
-                    }
-                }
-                CommandEvent::Error(err) => {
-                    eprintln!("[API ERROR] {}", err);
-                }
-                CommandEvent::Terminated(status) => {
-                    eprintln!("[API] Sidecar terminated with status: {:?}", status);
-                    BACKEND_READY.store(false, Ordering::SeqCst);
-                    let _ = app_handle.emit("backend-terminated", status.code);
-                    break;
-                }
-                _ => {}
-            }
-        }
-    });
+/// Whether the core is connected to the WebSocket of this window's backend.
+#[tauri::command]
+#[instrument(skip(window, profiles), fields(window = %window.label()))]
+fn is_ws_connected(window: WebviewWindow, profiles: State<'_, Profiles>) -> bool {
+    profiles
+        .for_window(window.label())
+        .is_ok_and(|backend| backend.bridge.is_connected())
+}
+
+/// Data source for the calling window's profile: its backend, or the state
+/// database if the backend never became ready.
+fn data_source(window: &WebviewWindow, profiles: &Profiles) -> Result<DataSource, ApiError> {
+    let backend = profiles.for_window(window.label())?;
+    DataSource::for_backend(window.app_handle(), &backend)
+}
+
+/// Where the data commands currently read from: `api` or `offline`.
+#[tauri::command]
+#[instrument(skip(window, profiles), fields(window = %window.label()))]
+fn get_data_source(window: WebviewWindow, profiles: State<'_, Profiles>) -> Result<&'static str, ApiError> {
+    Ok(data_source(&window, &profiles)?.kind())
+}
+
+/// `GET /api/tasks`
+#[tauri::command]
+#[instrument(skip(window, profiles), fields(window = %window.label()))]
+async fn list_tasks(
+    window: WebviewWindow,
+    profiles: State<'_, Profiles>,
+    query: Option<TaskQuery>,
+) -> Result<Vec<TaskSummary>, ApiError> {
+    data_source(&window, &profiles)?.tasks(&query.unwrap_or_default()).await
+}
+
+/// `GET /api/tasks/{task_id}`
+#[tauri::command]
+#[instrument(skip(window, profiles), fields(window = %window.label()))]
+async fn get_task(window: WebviewWindow, profiles: State<'_, Profiles>, task_id: String) -> Result<TaskDetail, ApiError> {
+    data_source(&window, &profiles)?.task(&task_id).await
+}
+
+/// `GET /api/tasks/{task_id}/registry`
+#[tauri::command]
+#[instrument(skip(window, profiles), fields(window = %window.label()))]
+async fn get_task_registry(
+    window: WebviewWindow,
+    profiles: State<'_, Profiles>,
+    task_id: String,
+) -> Result<serde_json::Value, ApiError> {
+    data_source(&window, &profiles)?.task_registry(&task_id).await
+}
+
+/// `GET /api/tasks/{task_id}/handovers`
+#[tauri::command]
+#[instrument(skip(window, profiles), fields(window = %window.label()))]
+async fn get_handovers(
+    window: WebviewWindow,
+    profiles: State<'_, Profiles>,
+    task_id: String,
+) -> Result<Vec<Handover>, ApiError> {
+    data_source(&window, &profiles)?.handovers(&task_id).await
+}
+
+/// `GET /api/agents/{task_id}`
+#[tauri::command]
+#[instrument(skip(window, profiles), fields(window = %window.label()))]
+async fn list_agents(
+    window: WebviewWindow,
+    profiles: State<'_, Profiles>,
+    task_id: String,
+    query: Option<AgentQuery>,
+) -> Result<Vec<AgentData>, ApiError> {
+    data_source(&window, &profiles)?
+        .agents(&task_id, &query.unwrap_or_default())
+        .await
+}
+
+/// `GET /api/agents/{task_id}/{agent_id}/progress`
+#[tauri::command]
+#[instrument(skip(window, profiles), fields(window = %window.label()))]
+async fn get_agent_progress(
+    window: WebviewWindow,
+    profiles: State<'_, Profiles>,
+    task_id: String,
+    agent_id: String,
+    limit: Option<u32>,
+) -> Result<Vec<AgentProgress>, ApiError> {
+    data_source(&window, &profiles)?
+        .agent_progress(&task_id, &agent_id, limit)
+        .await
+}
+
+/// `GET /api/agents/{task_id}/{agent_id}/findings`
+#[tauri::command]
+#[instrument(skip(window, profiles), fields(window = %window.label()))]
+async fn get_agent_findings(
+    window: WebviewWindow,
+    profiles: State<'_, Profiles>,
+    task_id: String,
+    agent_id: String,
+    query: Option<FindingQuery>,
+) -> Result<Vec<AgentFinding>, ApiError> {
+    data_source(&window, &profiles)?
+        .agent_findings(&task_id, &agent_id, &query.unwrap_or_default())
+        .await
+}
+
+/// `GET /api/agents/{task_id}/{agent_id}/logs`
+#[tauri::command]
+#[instrument(skip(window, profiles), fields(window = %window.label()))]
+async fn get_agent_logs(
+    window: WebviewWindow,
+    profiles: State<'_, Profiles>,
+    task_id: String,
+    agent_id: String,
+    query: Option<LogQuery>,
+) -> Result<Vec<LogEntry>, ApiError> {
+    data_source(&window, &profiles)?
+        .agent_logs(&task_id, &agent_id, &query.unwrap_or_default())
+        .await
+}
+
+/// `GET /api/agents/{task_id}/{agent_id}/output`
+#[tauri::command]
+#[instrument(skip(window, profiles), fields(window = %window.label()))]
+async fn get_agent_output(
+    window: WebviewWindow,
+    profiles: State<'_, Profiles>,
+    task_id: String,
+    agent_id: String,
+    query: Option<OutputQuery>,
+) -> Result<AgentOutput, ApiError> {
+    data_source(&window, &profiles)?
+        .agent_output(&task_id, &agent_id, &query.unwrap_or_default())
+        .await
+}
+
+/// `GET /api/phases/{task_id}`
+#[tauri::command]
+#[instrument(skip(window, profiles), fields(window = %window.label()))]
+async fn get_phases(
+    window: WebviewWindow,
+    profiles: State<'_, Profiles>,
+    task_id: String,
+) -> Result<Vec<PhaseRecord>, ApiError> {
+    data_source(&window, &profiles)?.phases(&task_id).await
+}
+
+/// `GET /api/tmux/sessions`
+#[tauri::command]
+#[instrument(skip(window, profiles), fields(window = %window.label()))]
+async fn list_tmux_sessions(
+    window: WebviewWindow,
+    profiles: State<'_, Profiles>,
+) -> Result<Vec<TmuxSessionInfo>, ApiError> {
+    data_source(&window, &profiles)?.tmux_sessions().await
+}
+
+/// Full-text search over the stream logs, progress and findings of the
+/// window's workspace, best hits first. Works without the backend.
+#[tauri::command]
+#[instrument(skip(window, profiles), fields(window = %window.label()))]
+async fn search(
+    window: WebviewWindow,
+    profiles: State<'_, Profiles>,
+    query: String,
+    filters: Option<SearchFilters>,
+) -> Result<Vec<SearchHit>, ApiError> {
+    let backend = profiles.for_window(window.label())?;
+    search::search(window.app_handle(), &backend, &query, filters.unwrap_or_default()).await
+}
+
+/// Follow an agent's stream log, sending batches of typed entries over
+/// `channel`. Returns the tail's ID for `ack_agent_log` and `stop_agent_log`.
+#[tauri::command]
+#[instrument(skip(window, profiles, tails, channel), fields(window = %window.label()))]
+async fn tail_agent_log(
+    window: WebviewWindow,
+    profiles: State<'_, Profiles>,
+    tails: State<'_, LogTails>,
+    task_id: String,
+    agent_id: String,
+    options: Option<TailOptions>,
+    channel: Channel<TailBatch>,
+) -> Result<u32, ApiError> {
+    let source = data_source(&window, &profiles)?;
+    // A log not created yet is followed once the agent starts writing it
+    let path = workspace::agent_file(&source, &task_id, &agent_id, AgentFile::Log).await?;
+    Ok(tails.start(
+        window.app_handle(),
+        window.label(),
+        path,
+        options.unwrap_or_default(),
+        channel,
+    ))
+}
+
+/// Acknowledge the batches of tail `id` up to `seq` once rendered; a tail
+/// stops reading while too many are unacknowledged.
+#[tauri::command]
+#[instrument(skip(window, tails), fields(window = %window.label()))]
+fn ack_agent_log(window: WebviewWindow, tails: State<'_, LogTails>, id: u32, seq: u64) {
+    tails.ack(id, seq);
+}
+
+#[tauri::command]
+#[instrument(skip(window, tails), fields(window = %window.label()))]
+fn stop_agent_log(window: WebviewWindow, tails: State<'_, LogTails>, id: u32) {
+    tails.stop(id);
+}
+
+/// Open an agent's tmux session in the terminal emulator set in the settings.
+#[tauri::command]
+#[instrument(skip(app, window, profiles), fields(window = %window.label()))]
+async fn open_agent_terminal(
+    app: AppHandle,
+    window: WebviewWindow,
+    profiles: State<'_, Profiles>,
+    task_id: String,
+    agent_id: String,
+) -> Result<(), ApiError> {
+    let source = data_source(&window, &profiles)?;
+    let agents = source.agents(&task_id, &AgentQuery::default()).await?;
+    let agent = agents
+        .iter()
+        .find(|agent| agent.id == agent_id)
+        .ok_or_else(|| ApiError::Status {
+            status: 404,
+            message: format!("Agent {} not found in task {}", agent_id, task_id),
+        })?;
+    if agent.tmux_session.is_empty() {
+        return Err(format!("Agent {} has no tmux session", agent_id).into());
+    }
+    let config = Settings::read(&app)?.external_terminal;
+    Ok(launcher::open_session(&app, &config, &agent.tmux_session)?)
+}
+
+/// Show a task's directory under one of the workspace bases in the file manager.
+#[tauri::command]
+#[instrument(skip(window, profiles), fields(window = %window.label()))]
+async fn reveal_task_workspace(
+    window: WebviewWindow,
+    profiles: State<'_, Profiles>,
+    task_id: String,
+) -> Result<(), ApiError> {
+    let bases = workspace_bases(&window, &profiles)?;
+    let source = data_source(&window, &profiles)?;
+    let dir = reveal::task_dir(&source, &bases, &task_id).await?;
+    Ok(reveal::open_dir(window.app_handle(), &dir)?)
+}
+
+/// Show one of an agent's files in the file manager, or open it at `line` in
+/// the editor set in the settings.
+#[tauri::command]
+#[instrument(skip(window, profiles), fields(window = %window.label()))]
+async fn open_agent_file(
+    window: WebviewWindow,
+    profiles: State<'_, Profiles>,
+    task_id: String,
+    agent_id: String,
+    file: AgentFile,
+    open_in: Option<OpenIn>,
+    line: Option<u32>,
+) -> Result<(), ApiError> {
+    let bases = workspace_bases(&window, &profiles)?;
+    let source = data_source(&window, &profiles)?;
+    let path = reveal::agent_file(&source, &bases, &task_id, &agent_id, file).await?;
+    let editor = Settings::read(window.app_handle())?.editor;
+    Ok(reveal::open_file(window.app_handle(), &editor, &path, open_in.unwrap_or_default(), line)?)
+}
+
+/// Workspace bases of the window's profile, one of which revealed paths must stay inside.
+fn workspace_bases(window: &WebviewWindow, profiles: &Profiles) -> Result<Vec<PathBuf>, String> {
+    let bases = profiles.for_window(window.label())?.workspace.bases(window.app_handle());
+    if bases.is_empty() {
+        return Err("No workspace is known".to_string());
+    }
+    Ok(bases)
+}
+
+/// Attach to an agent's tmux session in a PTY, sending its output as raw
+/// bytes over `channel`. Read-only unless `mode` is `takeover`.
+#[cfg(desktop)]
+#[tauri::command]
+#[instrument(skip(window, terminals, channel), fields(window = %window.label()))]
+fn open_terminal(
+    window: WebviewWindow,
+    terminals: State<'_, Terminals>,
+    session: String,
+    mode: Option<TerminalMode>,
+    cols: u16,
+    rows: u16,
+    channel: Channel<InvokeResponseBody>,
+) -> Result<u32, String> {
+    terminals.open(
+        window.app_handle(),
+        window.label(),
+        &session,
+        mode.unwrap_or_default(),
+        (cols, rows),
+        channel,
+    )
+}
+
+#[cfg(desktop)]
+#[tauri::command]
+#[instrument(skip(window, terminals, data), fields(window = %window.label()))]
+fn write_terminal(window: WebviewWindow, terminals: State<'_, Terminals>, id: u32, data: String) -> Result<(), String> {
+    terminals.write(id, &data)
+}
+
+#[cfg(desktop)]
+#[tauri::command]
+#[instrument(skip(window, terminals), fields(window = %window.label()))]
+fn resize_terminal(
+    window: WebviewWindow,
+    terminals: State<'_, Terminals>,
+    id: u32,
+    cols: u16,
+    rows: u16,
+) -> Result<(), String> {
+    terminals.resize(id, cols, rows)
+}
 
+/// Detach a terminal; the agent's session keeps running.
+#[cfg(desktop)]
+#[tauri::command]
+#[instrument(skip(window, terminals), fields(window = %window.label()))]
+fn close_terminal(window: WebviewWindow, terminals: State<'_, Terminals>, id: u32) {
+    terminals.close(id);
+}
+
+/// Which orchestration events raise native notifications.
+#[tauri::command]
+#[instrument(skip(window, notifications), fields(window = %window.label()))]
+fn get_notification_settings(window: WebviewWindow, notifications: State<'_, Notifications>) -> NotificationConfig {
+    notifications.config()
+}
+
+/// Change and persist which orchestration events raise native notifications.
+#[tauri::command]
+#[instrument(skip(app, window, notifications), fields(window = %window.label()))]
+fn set_notification_settings(
+    app: AppHandle,
+    window: WebviewWindow,
+    notifications: State<'_, Notifications>,
+    config: NotificationConfig,
+) -> Result<(), String> {
+    let value = serde_json::to_value(&config).map_err(|e| e.to_string())?;
+    Settings::update(&app, "notifications", value)?;
+    notifications.set_config(config);
+    info!("notification settings changed");
     Ok(())
 }
 
+/// Show the core and sidecar log files in the file manager.
+#[tauri::command]
+#[instrument(skip(app, window), fields(window = %window.label()))]
+fn reveal_logs(app: AppHandle, window: WebviewWindow) -> Result<(), String> {
+    let dir = logfile::dir().ok_or("Log files are not available")?;
+    let core_log = dir.join(logfile::CORE_LOG);
+
+    let result = if core_log.exists() {
+        app.opener().reveal_item_in_dir(&core_log)
+    } else {
+        app.opener().open_path(dir.to_string_lossy(), None::<&str>)
+    };
+    result.map_err(|e| format!("Failed to open {}: {}", dir.display(), e))
+}
+
+/// Kill the sidecars if the app panics - with `panic = "abort"` no exit
+/// handlers run, so this is the last chance to avoid an orphaned backend.
+fn install_panic_hook(app: AppHandle) {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        error!(panic = %info, "core panicked");
+        // A panic during setup can come before the profiles are managed
+        if let Some(profiles) = app.try_state::<Profiles>() {
+            profiles.kill_now();
+        }
+        default_hook(info);
+    }));
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    tauri::Builder::default()
+    let builder = tauri::Builder::default();
+
+    // Must be the first plugin: a second launch exits before spawning its own
+    // sidecar and hands its arguments to the running app instead
+    #[cfg(desktop)]
+    let builder = builder.plugin(tauri_plugin_single_instance::init(|app, argv, cwd| {
+        instance::on_second_instance(app, argv, cwd)
+    }));
+
+    let app = builder
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_deep_link::init())
+        .plugin(tauri_plugin_notification::init())
         .setup(|app| {
-            println!("[Tauri] Starting Claude Orchestrator Dashboard...");
-
-            // Spawn backend sidecar - don't crash if it fails
-            match spawn_backend(app.handle()) {
-                Ok(()) => println!("[Tauri] Backend sidecar spawned successfully"),
-                Err(e) => {
-                    eprintln!("[Tauri] Warning: Backend sidecar failed to start: {}", e);
-                    eprintln!("[Tauri] App will continue but backend features may not work");
-                }
+            // Logging is configured from the settings file, so problems with it
+            // are collected and reported once the subscriber is installed
+            let (settings, settings_error) = match Settings::read(app.handle()) {
+                Ok(settings) => (settings, None),
+                Err(e) => (Settings::default(), Some(e)),
+            };
+            let log_error = match app.path().app_log_dir() {
+                Ok(dir) => logfile::init(dir.clone(), &settings.log_files)
+                    .err()
+                    .map(|e| format!("Cannot write log files to {}: {}", dir.display(), e)),
+                Err(e) => Some(format!("No log directory available: {}", e)),
+            };
+            telemetry::init(&settings.logging);
+            for error in [settings_error, log_error].into_iter().flatten() {
+                warn!("{}", error);
             }
 
-            // Wait for backend in background, then notify frontend
-            let app_handle = app.handle().clone();
-            tauri::async_runtime::spawn(async move {
-                if wait_for_backend_ready().await {
-                    BACKEND_READY.store(true, Ordering::SeqCst);
-                    let _ = app_handle.emit("backend-ready", true);
-                    println!("[Tauri] Backend is ready, frontend can connect");
-                } else {
-                    let _ = app_handle.emit("backend-failed", "Backend failed to start");
-                    eprintln!("[Tauri] Backend failed to become ready");
-                }
-            });
+            info!(version = env!("CARGO_PKG_VERSION"), "starting Claude Orchestrator Dashboard");
+
+            install_panic_hook(app.handle().clone());
+
+            deeplink::init(app.handle());
+
+            app.manage(Notifications::new(settings.notifications.clone()));
+            app.manage(LogTails::default());
+            #[cfg(desktop)]
+            app.manage(Terminals::default());
+
+            let profiles = Profiles::from_settings(&settings);
+            let default_profile = profiles.default_profile();
+            app.manage(profiles);
+            app.state::<Profiles>().activate(app.handle(), &default_profile)?;
+
+            #[cfg(desktop)]
+            tray::init(app.handle())?;
 
             Ok(())
         })
+        .on_window_event(|window, event| match event {
+            // The tray keeps the app running, so closing the main window only hides it
+            #[cfg(desktop)]
+            WindowEvent::CloseRequested { api, .. } if window.label() == instance::MAIN_WINDOW => {
+                api.prevent_close();
+                if let Err(e) = window.hide() {
+                    warn!(error = %e, "failed to hide main window");
+                }
+            }
+            #[cfg(windows)]
+            WindowEvent::Focused(true) => notifications::on_focus(window),
+            WindowEvent::Destroyed => {
+                window.state::<Profiles>().forget_window(window.label());
+                window.state::<LogTails>().forget_window(window.label());
+                #[cfg(desktop)]
+                window.state::<Terminals>().forget_window(window.label());
+            }
+            _ => {}
+        })
         .invoke_handler(tauri::generate_handler![
             get_backend_url,
             get_ws_url,
-            is_backend_ready
+            is_backend_ready,
+            get_backend_state,
+            get_backend_logs,
+            take_pending_navigation,
+            list_profiles,
+            get_profile,
+            activate_profile,
+            deactivate_profile,
+            switch_profile,
+            open_profile_window,
+            get_workspace,
+            set_workspace,
+            pick_workspace,
+            get_log_level,
+            set_log_level,
+            get_data_source,
+            list_tasks,
+            get_task,
+            get_task_registry,
+            get_handovers,
+            list_agents,
+            get_agent_progress,
+            get_agent_findings,
+            get_agent_logs,
+            get_agent_output,
+            get_phases,
+            list_tmux_sessions,
+            search,
+            tail_agent_log,
+            ack_agent_log,
+            stop_agent_log,
+            open_agent_terminal,
+            reveal_task_workspace,
+            open_agent_file,
+            #[cfg(desktop)]
+            open_terminal,
+            #[cfg(desktop)]
+            write_terminal,
+            #[cfg(desktop)]
+            resize_terminal,
+            #[cfg(desktop)]
+            close_terminal,
+            ws_subscribe,
+            ws_unsubscribe,
+            is_ws_connected,
+            get_notification_settings,
+            set_notification_settings,
+            reveal_logs
         ])
-        .run(tauri::generate_context!())
+        .build(tauri::generate_context!())
         .expect("Error running Claude Orchestrator Dashboard");
+
+    app.run(|app_handle, event| match event {
+        // Stop the sidecars before the process goes away so they don't keep
+        // holding their ports
+        RunEvent::ExitRequested { .. } | RunEvent::Exit => {
+            let profiles = app_handle.state::<Profiles>();
+            tauri::async_runtime::block_on(profiles.shutdown(app_handle));
+        }
+        _ => {}
+    });
 }
@@ -0,0 +1,166 @@
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use tracing_subscriber::fmt::MakeWriter;
+
+use crate::logs::LogLine;
+
+/// Log file for the desktop core's own messages
+pub(crate) const CORE_LOG: &str = "core.log";
+
+/// Log file for sidecar stdout/stderr
+pub(crate) const BACKEND_LOG: &str = "backend.log";
+
+static LOG_FILES: OnceLock<LogFiles> = OnceLock::new();
+
+/// Rotation limits, from the `logFiles` section of the settings file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct LogFileConfig {
+    /// Rotate a file once it grows past this size
+    pub max_bytes: u64,
+    /// Rotated files kept per log, besides the active one
+    pub max_files: usize,
+}
+
+impl Default for LogFileConfig {
+    fn default() -> Self {
+        Self {
+            max_bytes: 5 * 1024 * 1024,
+            max_files: 5,
+        }
+    }
+}
+
+/// Append-only file rotated to `name.1`, `name.2`, ... by size.
+struct RotatingFile {
+    path: PathBuf,
+    config: LogFileConfig,
+    file: Mutex<Option<(File, u64)>>,
+}
+
+impl RotatingFile {
+    fn new(path: PathBuf, config: LogFileConfig) -> Self {
+        Self {
+            path,
+            config,
+            file: Mutex::new(None),
+        }
+    }
+
+    fn write_line(&self, line: &str) {
+        self.write_bytes(format!("{}\n", line).as_bytes());
+    }
+
+    /// Append already formatted output, rotating first if the file is full.
+    fn write_bytes(&self, buf: &[u8]) {
+        let mut guard = self.file.lock().unwrap_or_else(|e| e.into_inner());
+
+        if guard.as_ref().is_some_and(|(_, size)| *size >= self.config.max_bytes) {
+            *guard = None;
+            self.rotate();
+        }
+        if guard.is_none() {
+            *guard = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)
+                .ok()
+                .map(|file| {
+                    let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+                    (file, size)
+                });
+        }
+
+        if let Some((file, size)) = guard.as_mut() {
+            if file.write_all(buf).is_ok() {
+                *size += buf.len() as u64;
+            }
+        }
+    }
+
+    /// Shift `name.N-1` to `name.N`, dropping the oldest, then move the active file to `name.1`.
+    fn rotate(&self) {
+        let rotated = |n: usize| PathBuf::from(format!("{}.{}", self.path.display(), n));
+
+        if self.config.max_files == 0 {
+            let _ = fs::remove_file(&self.path);
+            return;
+        }
+        let _ = fs::remove_file(rotated(self.config.max_files));
+        for n in (1..self.config.max_files).rev() {
+            let _ = fs::rename(rotated(n), rotated(n + 1));
+        }
+        let _ = fs::rename(&self.path, rotated(1));
+    }
+}
+
+struct LogFiles {
+    dir: PathBuf,
+    core: RotatingFile,
+    backend: RotatingFile,
+}
+
+/// Start writing logs to `dir`. Messages logged before this are console-only.
+pub(crate) fn init(dir: PathBuf, config: &LogFileConfig) -> std::io::Result<()> {
+    fs::create_dir_all(&dir)?;
+    let files = LogFiles {
+        core: RotatingFile::new(dir.join(CORE_LOG), config.clone()),
+        backend: RotatingFile::new(dir.join(BACKEND_LOG), config.clone()),
+        dir,
+    };
+    let _ = LOG_FILES.set(files);
+    Ok(())
+}
+
+/// Directory the log files are written to, once initialized.
+pub(crate) fn dir() -> Option<&'static Path> {
+    LOG_FILES.get().map(|files| files.dir.as_path())
+}
+
+fn timestamp() -> String {
+    chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f").to_string()
+}
+
+/// Append a line of a profile's sidecar output to `backend.log`.
+pub(crate) fn backend(profile: &str, line: &LogLine) {
+    if let Some(files) = LOG_FILES.get() {
+        files.backend.write_line(&format!(
+            "{} [{}] {:<6} {:<8} {}",
+            timestamp(),
+            profile,
+            line.stream.as_str(),
+            line.level.as_str(),
+            line.message
+        ));
+    }
+}
+
+/// `MakeWriter` for the tracing subscriber that appends to `core.log`.
+///
+/// Writes are dropped until `init` has run.
+#[derive(Clone, Copy)]
+pub(crate) struct CoreLogWriter;
+
+impl<'a> MakeWriter<'a> for CoreLogWriter {
+    type Writer = CoreLogWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        *self
+    }
+}
+
+impl Write for CoreLogWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if let Some(files) = LOG_FILES.get() {
+            files.core.write_bytes(buf);
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
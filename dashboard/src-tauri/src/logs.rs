@@ -0,0 +1,188 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::AppHandle;
+
+use crate::logfile;
+use crate::profiles::Backend;
+use crate::telemetry;
+
+/// Lines kept when the settings file doesn't say otherwise
+pub(crate) const DEFAULT_LOG_CAPACITY: usize = 2000;
+
+/// Which sidecar stream a line came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum LogStream {
+    Stdout,
+    Stderr,
+}
+
+impl LogStream {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Stdout => "stdout",
+            Self::Stderr => "stderr",
+        }
+    }
+}
+
+/// Severity detected from the line's prefix, ordered from least to most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum LogLevel {
+    Debug,
+    Info,
+    Warning,
+    Error,
+    Critical,
+}
+
+impl LogLevel {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Debug => "DEBUG",
+            Self::Info => "INFO",
+            Self::Warning => "WARNING",
+            Self::Error => "ERROR",
+            Self::Critical => "CRITICAL",
+        }
+    }
+}
+
+/// One line of sidecar output.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LogLine {
+    /// Monotonic sequence number, usable as a `since` cursor
+    pub seq: u64,
+    /// Unix milliseconds the line was received
+    pub timestamp: u64,
+    pub stream: LogStream,
+    pub level: LogLevel,
+    pub message: String,
+}
+
+struct Buffer {
+    lines: VecDeque<LogLine>,
+    next_seq: u64,
+    /// Level of the previous line per stream, for traceback continuations
+    last_level: [LogLevel; 2],
+}
+
+/// `backend-log` event payload: a line tagged with the profile it came from.
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BackendLogEvent<'a> {
+    profile: &'a str,
+    #[serde(flatten)]
+    line: LogLine,
+}
+
+/// Ring buffer with the last lines of a profile's sidecar output.
+pub(crate) struct BackendLogs {
+    capacity: usize,
+    buffer: Mutex<Buffer>,
+}
+
+impl BackendLogs {
+    pub(crate) fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            capacity,
+            buffer: Mutex::new(Buffer {
+                lines: VecDeque::with_capacity(capacity),
+                next_seq: 1,
+                last_level: [LogLevel::Info; 2],
+            }),
+        }
+    }
+
+    /// Record a line, evicting the oldest once full.
+    pub(crate) fn push(&self, stream: LogStream, message: &str) -> LogLine {
+        let message = message.trim_end_matches(['\r', '\n']).to_string();
+        let mut buffer = self.buffer.lock().unwrap();
+
+        let slot = stream as usize;
+        let level = detect_level(&message).unwrap_or_else(|| {
+            // Indented lines continue the previous entry (tracebacks, wrapped output)
+            if message.starts_with([' ', '\t']) {
+                buffer.last_level[slot]
+            } else {
+                LogLevel::Info
+            }
+        });
+        buffer.last_level[slot] = level;
+
+        let line = LogLine {
+            seq: buffer.next_seq,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0),
+            stream,
+            level,
+            message,
+        };
+        buffer.next_seq += 1;
+
+        if buffer.lines.len() == self.capacity {
+            buffer.lines.pop_front();
+        }
+        buffer.lines.push_back(line.clone());
+        line
+    }
+
+    /// Lines after sequence number `since` at or above `level`.
+    pub(crate) fn query(&self, since: Option<u64>, level: Option<LogLevel>) -> Vec<LogLine> {
+        let since = since.unwrap_or(0);
+        let level = level.unwrap_or(LogLevel::Debug);
+        self.buffer
+            .lock()
+            .unwrap()
+            .lines
+            .iter()
+            .filter(|line| line.seq > since && line.level >= level)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Store a line of sidecar output, append it to `backend.log` and stream it to the frontend.
+pub(crate) fn record(app: &AppHandle, backend: &Backend, stream: LogStream, message: &str) {
+    let line = backend.logs.push(stream, message);
+    logfile::backend(&backend.profile, &line);
+    telemetry::emit(
+        app,
+        "backend-log",
+        BackendLogEvent {
+            profile: &backend.profile,
+            line,
+        },
+    );
+}
+
+/// Level from uvicorn/logging prefixes (`INFO:`, `ERROR:`, `[Warning]`) or a traceback header.
+fn detect_level(message: &str) -> Option<LogLevel> {
+    let trimmed = message.trim_start_matches('[');
+    let word: String = trimmed
+        .chars()
+        .take_while(|c| c.is_ascii_alphabetic())
+        .collect::<String>()
+        .to_ascii_uppercase();
+
+    match word.as_str() {
+        "DEBUG" => Some(LogLevel::Debug),
+        "INFO" => Some(LogLevel::Info),
+        "WARN" | "WARNING" => Some(LogLevel::Warning),
+        "ERROR" => Some(LogLevel::Error),
+        "CRITICAL" | "FATAL" => Some(LogLevel::Critical),
+        "TRACEBACK" => Some(LogLevel::Error),
+        _ => {
+            // Last line of a traceback, e.g. `KeyError: 'task_id'`
+            let head = message.split_whitespace().next().unwrap_or("");
+            (head.ends_with("Error:") || head.ends_with("Exception:")).then_some(LogLevel::Error)
+        }
+    }
+}
@@ -0,0 +1,407 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+// Mirrors of dashboard/backend/models/schemas.py. Field names keep the API's
+// snake_case so the frontend sees the same shapes as over HTTP.
+
+/// ISO 8601 timestamp as sent by the backend, kept as text since it mixes
+/// naive and offset-aware ones
+pub(crate) type Timestamp = String;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub(crate) enum TaskStatus {
+    Initialized,
+    Active,
+    Completed,
+    Failed,
+    Cancelled,
+    Archived,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum Priority {
+    P0,
+    P1,
+    P2,
+    P3,
+    P4,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub(crate) enum PhaseStatus {
+    Pending,
+    Active,
+    InReview,
+    Approved,
+    RevisionNeeded,
+    Fixing,
+    Escalated,
+    Failed,
+    // Legacy statuses retained for old persisted tasks
+    AwaitingReview,
+    UnderReview,
+    Rejected,
+    Revising,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum AgentStatus {
+    Running,
+    Working,
+    Blocked,
+    Reviewing,
+    Completed,
+    PhaseCompleted,
+    Failed,
+    Error,
+    Terminated,
+    Killed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum FindingType {
+    Issue,
+    Solution,
+    Insight,
+    Recommendation,
+    Blocker,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum Severity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ReviewStatus {
+    Pending,
+    InProgress,
+    Completed,
+    Aborted,
+    Failed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum Verdict {
+    Approved,
+    Rejected,
+    NeedsRevision,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum LogEntryType {
+    System,
+    User,
+    Assistant,
+    ToolCall,
+    ToolResult,
+}
+
+/// `PhaseData`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct PhaseData {
+    pub id: String,
+    pub order: i64,
+    pub name: String,
+    pub description: Option<String>,
+    pub status: PhaseStatus,
+    pub created_at: Timestamp,
+    pub started_at: Option<Timestamp>,
+    pub completed_at: Option<Timestamp>,
+}
+
+/// `TrackedFiles`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct TrackedFiles {
+    pub prompt_file: Option<String>,
+    pub log_file: Option<String>,
+    pub progress_file: Option<String>,
+    pub findings_file: Option<String>,
+    pub deploy_log: Option<String>,
+}
+
+/// `AgentData`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct AgentData {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub tmux_session: String,
+    pub parent: String,
+    pub depth: i64,
+    pub phase_index: i64,
+    pub status: AgentStatus,
+    pub started_at: Timestamp,
+    pub completed_at: Option<Timestamp>,
+    pub progress: u8,
+    pub last_update: Timestamp,
+    /// First 200 characters of the agent's prompt
+    pub prompt: String,
+    pub claude_pid: Option<i64>,
+    pub cursor_pid: Option<i64>,
+    pub tracked_files: TrackedFiles,
+}
+
+/// `AgentProgress`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct AgentProgress {
+    pub timestamp: Timestamp,
+    pub agent_id: String,
+    pub status: String,
+    pub message: String,
+    pub progress: u8,
+}
+
+/// `AgentFinding`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct AgentFinding {
+    pub timestamp: Timestamp,
+    pub agent_id: String,
+    pub finding_type: FindingType,
+    pub severity: Severity,
+    pub message: String,
+    pub data: Option<Map<String, Value>>,
+}
+
+/// `ReviewData`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ReviewData {
+    pub review_id: String,
+    pub phase_index: i64,
+    pub status: ReviewStatus,
+    pub started_at: Timestamp,
+    pub reviewer_count: i64,
+    pub verdicts_submitted: i64,
+    pub final_verdict: Option<Verdict>,
+}
+
+/// `TaskContext`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct TaskContext {
+    pub expected_deliverables: Option<Vec<String>>,
+    pub success_criteria: Option<Vec<String>>,
+    pub relevant_files: Option<Vec<String>>,
+}
+
+/// `TaskSummary`, an entry of `GET /api/tasks`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct TaskSummary {
+    pub task_id: String,
+    pub description: String,
+    pub created_at: Timestamp,
+    pub status: TaskStatus,
+    pub current_phase: Option<PhaseData>,
+    pub agent_count: i64,
+    pub active_agents: i64,
+    #[serde(default)]
+    pub failed_agents: i64,
+    pub progress: u8,
+}
+
+/// `TaskDetail`, from `GET /api/tasks/{task_id}`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct TaskDetail {
+    pub task_id: String,
+    pub task_description: String,
+    pub created_at: Timestamp,
+    pub workspace: String,
+    pub workspace_base: String,
+    pub client_cwd: String,
+    pub status: TaskStatus,
+    pub priority: Priority,
+    pub phases: Vec<PhaseData>,
+    pub current_phase_index: i64,
+    pub agents: Vec<AgentData>,
+    pub agent_hierarchy: HashMap<String, Vec<String>>,
+    pub max_agents: i64,
+    pub max_depth: i64,
+    pub max_concurrent: i64,
+    pub total_spawned: i64,
+    pub active_count: i64,
+    pub completed_count: i64,
+    pub reviews: Vec<ReviewData>,
+    pub task_context: TaskContext,
+}
+
+/// `LogEntry`, from `GET /api/agents/{task_id}/{agent_id}/logs`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct LogEntry {
+    pub timestamp: Timestamp,
+    #[serde(rename = "type")]
+    pub kind: LogEntryType,
+    pub content: Option<String>,
+    pub subtype: Option<String>,
+    pub tool_name: Option<String>,
+    pub parameters: Option<Map<String, Value>>,
+    pub result: Option<Value>,
+}
+
+/// Row of the `phases` table, as `GET /api/phases/{task_id}` returns it.
+///
+/// Older tasks without a state database return registry phases instead, so
+/// everything but the name is optional.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct PhaseRecord {
+    #[serde(default)]
+    pub task_id: Option<String>,
+    #[serde(default)]
+    pub phase_index: Option<i64>,
+    #[serde(default, alias = "id")]
+    pub phase_id: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub deliverables: Vec<String>,
+    #[serde(default)]
+    pub success_criteria: Vec<String>,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub created_at: Option<Timestamp>,
+    #[serde(default)]
+    pub started_at: Option<Timestamp>,
+    #[serde(default)]
+    pub completed_at: Option<Timestamp>,
+    #[serde(default)]
+    pub revision_round: Option<i64>,
+    #[serde(default)]
+    pub max_revision_rounds: Option<i64>,
+    #[serde(default)]
+    pub last_review_id: Option<String>,
+    #[serde(default)]
+    pub revision_ready: Option<i64>,
+}
+
+/// Entry of `GET /api/tmux/sessions`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct TmuxSessionInfo {
+    pub name: String,
+    pub session_id: Option<String>,
+    pub created_at: Option<Timestamp>,
+    pub pid: Option<i64>,
+    pub agent_id: Option<String>,
+    pub status: String,
+}
+
+/// `GET /api/agents/{task_id}/{agent_id}/output`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct AgentOutput {
+    pub output: String,
+    pub metadata: AgentOutputMetadata,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct AgentOutputMetadata {
+    pub file_exists: bool,
+    #[serde(default)]
+    pub file_size: Option<u64>,
+    #[serde(default)]
+    pub lines_read: Option<u64>,
+    #[serde(default)]
+    pub response_format: Option<String>,
+    pub task_id: String,
+    pub agent_id: String,
+}
+
+/// Row of the `handovers` table, from `GET /api/tasks/{task_id}/handovers`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Handover {
+    pub handover_id: i64,
+    pub task_id: String,
+    pub from_phase_index: i64,
+    pub to_phase_index: i64,
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub key_findings: Vec<Value>,
+    #[serde(default)]
+    pub blockers: Vec<Value>,
+    #[serde(default)]
+    pub recommendations: Vec<Value>,
+    pub created_at: Option<Timestamp>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::de::DeserializeOwned;
+
+    use crate::health::HealthInfo;
+
+    /// Decode a fixture of `dashboard/contract` and check that encoding it
+    /// again gives back the same JSON, so no field is dropped or renamed.
+    fn round_trip<T: DeserializeOwned + Serialize>(fixture: &str) {
+        let expected: Value = serde_json::from_str(fixture).unwrap();
+        let decoded: T = serde_json::from_value(expected.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&decoded).unwrap(), expected);
+    }
+
+    #[test]
+    fn tasks() {
+        round_trip::<Vec<TaskSummary>>(include_str!("../../contract/tasks.json"));
+    }
+
+    #[test]
+    fn task_detail() {
+        round_trip::<TaskDetail>(include_str!("../../contract/task_detail.json"));
+    }
+
+    #[test]
+    fn agents() {
+        round_trip::<Vec<AgentData>>(include_str!("../../contract/agents.json"));
+    }
+
+    #[test]
+    fn agent_progress() {
+        round_trip::<Vec<AgentProgress>>(include_str!("../../contract/agent_progress.json"));
+    }
+
+    #[test]
+    fn agent_findings() {
+        round_trip::<Vec<AgentFinding>>(include_str!("../../contract/agent_findings.json"));
+    }
+
+    #[test]
+    fn agent_logs() {
+        round_trip::<Vec<LogEntry>>(include_str!("../../contract/agent_logs.json"));
+    }
+
+    #[test]
+    fn agent_output() {
+        round_trip::<AgentOutput>(include_str!("../../contract/agent_output.json"));
+    }
+
+    #[test]
+    fn tmux_sessions() {
+        round_trip::<Vec<TmuxSessionInfo>>(include_str!("../../contract/tmux_sessions.json"));
+    }
+
+    #[test]
+    fn phases() {
+        round_trip::<Vec<PhaseRecord>>(include_str!("../../contract/phases.json"));
+    }
+
+    #[test]
+    fn handovers() {
+        round_trip::<Vec<Handover>>(include_str!("../../contract/handovers.json"));
+    }
+
+    #[test]
+    fn health() {
+        let health: HealthInfo = serde_json::from_str(include_str!("../../contract/health.json")).unwrap();
+        assert!(health.is_ours());
+    }
+}
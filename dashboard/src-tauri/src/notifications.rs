@@ -0,0 +1,410 @@
+use serde::{Deserialize, Serialize};
+#[cfg(windows)]
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Mutex;
+#[cfg(windows)]
+use std::time::{Duration, Instant};
+#[cfg(windows)]
+use tauri::Window;
+use tauri::{AppHandle, Manager};
+#[cfg(not(all(unix, not(any(target_os = "android", target_os = "ios")))))]
+use tauri_plugin_notification::NotificationExt;
+use tracing::{debug, info, warn};
+
+#[cfg(all(unix, not(any(target_os = "android", target_os = "ios"))))]
+use crate::deeplink::PendingNavigation;
+use crate::deeplink::NavigateTarget;
+use crate::profiles::{Backend, Profiles};
+#[cfg(any(windows, all(unix, not(any(target_os = "android", target_os = "ios")))))]
+use crate::telemetry;
+
+/// How long after a notification focusing the app counts as clicking it
+#[cfg(windows)]
+const CLICK_WINDOW: Duration = Duration::from_secs(30);
+
+/// Notified events remembered to avoid repeats
+const MAX_REMEMBERED: usize = 1_000;
+
+/// Longest finding message shown in a notification
+const MAX_BODY_CHARS: usize = 200;
+
+/// Subscription targets the notifier listens to, for every entity
+pub(crate) const SUBSCRIPTIONS: &[&str] = &["task", "agent", "phase"];
+
+/// Which orchestration events raise a native notification, from the
+/// `notifications` section of the settings file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct NotificationConfig {
+    /// Raise notifications at all
+    pub enabled: bool,
+    /// An agent reaching `failed`, `error` or `killed`
+    pub agent_failed: bool,
+    /// A phase waiting for review
+    pub review_requested: bool,
+    /// Findings of these severities
+    pub finding_severities: Vec<String>,
+    /// A task completing
+    pub task_completed: bool,
+    /// Also notify while a window showing the backend's profile is focused
+    pub when_focused: bool,
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            agent_failed: true,
+            review_requested: true,
+            finding_severities: vec!["critical".to_string()],
+            task_completed: true,
+            when_focused: false,
+        }
+    }
+}
+
+/// Notification settings in effect, and the events already notified.
+pub(crate) struct Notifications {
+    config: Mutex<NotificationConfig>,
+    /// `<profile>:<event key>` of events already notified
+    notified: Mutex<HashSet<String>>,
+    /// Target of each profile's last notification, and when it was shown
+    #[cfg(windows)]
+    shown: Mutex<HashMap<String, (NavigateTarget, Instant)>>,
+}
+
+impl Notifications {
+    pub(crate) fn new(config: NotificationConfig) -> Self {
+        Self {
+            config: Mutex::new(config),
+            notified: Mutex::new(HashSet::new()),
+            #[cfg(windows)]
+            shown: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn config(&self) -> NotificationConfig {
+        self.config.lock().unwrap().clone()
+    }
+
+    pub(crate) fn set_config(&self, config: NotificationConfig) {
+        *self.config.lock().unwrap() = config;
+    }
+}
+
+/// Messages from the backend's `/ws` that can raise a notification.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ServerEvent {
+    TaskUpdate {
+        task_id: Option<String>,
+        #[serde(default)]
+        data: TaskData,
+    },
+    AgentUpdate {
+        agent_id: String,
+        task_id: Option<String>,
+        #[serde(default)]
+        data: AgentData,
+    },
+    PhaseChange {
+        task_id: String,
+        phase: PhaseData,
+    },
+    FindingReported {
+        agent_id: String,
+        finding: FindingData,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct TaskData {
+    status: Option<String>,
+    task_description: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct AgentData {
+    status: Option<String>,
+    task_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PhaseData {
+    id: Option<String>,
+    name: String,
+    status: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct FindingData {
+    severity: String,
+    message: String,
+    task_id: Option<String>,
+    timestamp: Option<String>,
+}
+
+/// A notification to raise, with what it links to.
+#[derive(Debug)]
+struct Notice {
+    /// Identifies the event so repeated broadcasts notify once
+    key: String,
+    title: String,
+    body: String,
+    target: Option<NavigateTarget>,
+}
+
+impl ServerEvent {
+    fn notice(self, config: &NotificationConfig) -> Option<Notice> {
+        match self {
+            Self::AgentUpdate { agent_id, task_id, data } => {
+                let status = data.status?.to_lowercase();
+                if !config.agent_failed || !matches!(status.as_str(), "failed" | "error" | "killed") {
+                    return None;
+                }
+                let task_id = task_id.or(data.task_id);
+                Some(Notice {
+                    key: format!("agent:{}:{}", agent_id, status),
+                    title: "Agent failed".to_string(),
+                    body: match &task_id {
+                        Some(task_id) => format!("{} in {} is {}", agent_id, task_id, status),
+                        None => format!("{} is {}", agent_id, status),
+                    },
+                    target: task_id.map(|task_id| NavigateTarget::Agent { task_id, agent_id }),
+                })
+            }
+            Self::PhaseChange { task_id, phase } => {
+                let status = phase.status.to_uppercase();
+                let awaiting = matches!(status.as_str(), "AWAITING_REVIEW" | "IN_REVIEW" | "UNDER_REVIEW");
+                if !config.review_requested || !awaiting {
+                    return None;
+                }
+                Some(Notice {
+                    key: format!("phase:{}:{}:review", task_id, phase.id.as_deref().unwrap_or(&phase.name)),
+                    title: "Review needed".to_string(),
+                    body: format!("Phase {} of {} is waiting for review", phase.name, task_id),
+                    target: Some(NavigateTarget::Task { task_id }),
+                })
+            }
+            Self::FindingReported { agent_id, finding } => {
+                let severity = finding.severity.to_lowercase();
+                if !config.finding_severities.iter().any(|s| s.eq_ignore_ascii_case(&severity)) {
+                    return None;
+                }
+                let mut body: String = finding.message.chars().take(MAX_BODY_CHARS).collect();
+                if body.len() < finding.message.len() {
+                    body.push('…');
+                }
+                Some(Notice {
+                    key: format!("finding:{}:{}:{}", agent_id, finding.timestamp.unwrap_or_default(), finding.message),
+                    title: format!("{} finding from {}", capitalize(&severity), agent_id),
+                    body,
+                    target: finding.task_id.map(|task_id| NavigateTarget::Agent { task_id, agent_id }),
+                })
+            }
+            Self::TaskUpdate { task_id, data } => {
+                let task_id = task_id?;
+                let status = data.status?.to_uppercase();
+                if !config.task_completed || status != "COMPLETED" {
+                    return None;
+                }
+                Some(Notice {
+                    key: format!("task:{}:{}", task_id, status),
+                    title: "Task completed".to_string(),
+                    body: data.task_description.unwrap_or_else(|| task_id.clone()),
+                    target: Some(NavigateTarget::Task { task_id }),
+                })
+            }
+            Self::Other => None,
+        }
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Raise a notification for a message from the backend's `/ws`, if configured.
+pub(crate) fn handle(app: &AppHandle, backend: &Backend, message: &serde_json::Value) {
+    let notifications = app.state::<Notifications>();
+    let config = notifications.config();
+    if !config.enabled {
+        return;
+    }
+    let Some(notice) = ServerEvent::deserialize(message)
+        .ok()
+        .and_then(|event| event.notice(&config))
+    else {
+        return;
+    };
+
+    let first = {
+        let mut notified = notifications.notified.lock().unwrap();
+        if notified.len() >= MAX_REMEMBERED {
+            notified.clear();
+        }
+        notified.insert(format!("{}:{}", backend.profile, notice.key))
+    };
+    if first {
+        show(app, backend, notice, config.when_focused);
+    }
+}
+
+fn show(app: &AppHandle, backend: &Backend, notice: Notice, when_focused: bool) {
+    if !when_focused && profile_focused(app, &backend.profile) {
+        debug!(key = %notice.key, "window is focused, not notifying");
+        return;
+    }
+    deliver(app, backend.profile.clone(), notice);
+}
+
+/// Show `notice` through the desktop notification server, and open its target
+/// when the notification is clicked.
+#[cfg(all(unix, not(any(target_os = "macos", target_os = "android", target_os = "ios"))))]
+fn deliver(app: &AppHandle, profile: String, notice: Notice) {
+    let app = app.clone();
+    // Waiting for the click blocks until the notification is closed
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut notification = notify_rust::Notification::new();
+        notification
+            .appname(&app.package_info().name)
+            .summary(&notice.title)
+            .body(&notice.body)
+            .auto_icon();
+        if notice.target.is_some() {
+            // Clicking the notification itself invokes the `default` action
+            notification.action("default", "Open");
+        }
+        let handle = match notification.show() {
+            Ok(handle) => handle,
+            Err(e) => return warn!(key = %notice.key, error = %e, "failed to show notification"),
+        };
+        info!(key = %notice.key, "notified");
+        if let Some(target) = notice.target {
+            handle.wait_for_action(|action| {
+                if action == "default" {
+                    open(&app, &profile, target);
+                }
+            });
+        }
+    });
+}
+
+/// Show `notice` through the notification center, and open its target when
+/// the notification is clicked.
+#[cfg(target_os = "macos")]
+fn deliver(app: &AppHandle, profile: String, notice: Notice) {
+    use mac_notification_sys::NotificationResponse;
+
+    let app = app.clone();
+    // Waiting for the click blocks until the notification is dismissed
+    tauri::async_runtime::spawn_blocking(move || {
+        // Development builds have no bundle of their own to post as, as in the plugin
+        let bundle = if tauri::is_dev() {
+            "com.apple.Terminal"
+        } else {
+            app.config().identifier.as_str()
+        };
+        // Fails once the application is set, which is fine
+        let _ = mac_notification_sys::set_application(bundle);
+        let response = mac_notification_sys::Notification::new()
+            .title(&notice.title)
+            .message(&notice.body)
+            .wait_for_click(notice.target.is_some())
+            .send();
+        match response {
+            Ok(response) => {
+                info!(key = %notice.key, "notified");
+                if let (NotificationResponse::Click, Some(target)) = (response, notice.target) {
+                    open(&app, &profile, target);
+                }
+            }
+            Err(e) => warn!(key = %notice.key, error = %e, "failed to show notification"),
+        }
+    });
+}
+
+/// Show `notice` through the notification plugin. Where the platform reports
+/// taps (mobile), the plugin hands `extra` to the frontend's `onAction`
+/// listener; Windows toasts report none, so a focus shortly after opens the
+/// target instead (see [`on_focus`]).
+#[cfg(not(all(unix, not(any(target_os = "android", target_os = "ios")))))]
+fn deliver(app: &AppHandle, profile: String, notice: Notice) {
+    let mut builder = app
+        .notification()
+        .builder()
+        .title(&notice.title)
+        .body(&notice.body)
+        .extra("profile", &profile);
+    if let Some(target) = &notice.target {
+        builder = builder.extra("navigate", target);
+    }
+    match builder.show() {
+        Ok(()) => info!(key = %notice.key, "notified"),
+        Err(e) => return warn!(key = %notice.key, error = %e, "failed to show notification"),
+    }
+
+    #[cfg(windows)]
+    if let Some(target) = notice.target {
+        let notifications = app.state::<Notifications>();
+        notifications.shown.lock().unwrap().insert(profile, (target, Instant::now()));
+    }
+}
+
+/// Bring forward a window showing `profile` and open `target` in it.
+#[cfg(all(unix, not(any(target_os = "android", target_os = "ios"))))]
+fn open(app: &AppHandle, profile: &str, target: NavigateTarget) {
+    info!(profile, navigate = ?target, "opening notification");
+    let profiles = app.state::<Profiles>();
+    let window = app
+        .webview_windows()
+        .into_values()
+        .find(|window| profiles.window_profile(window.label()) == profile);
+    match window {
+        Some(window) => {
+            let _ = window.unminimize();
+            let _ = window.show();
+            if let Err(e) = window.set_focus() {
+                warn!(error = %e, "failed to focus window");
+            }
+            telemetry::emit_to(app, window.label(), "navigate", target);
+        }
+        None => {
+            // The new window's page takes the target once it loads
+            app.state::<PendingNavigation>().set(target);
+            if let Err(e) = profiles.open_window(app, profile) {
+                warn!(profile, error = %e, "failed to open notification");
+            }
+        }
+    }
+}
+
+/// Called when a window gains focus. Windows toasts don't report clicks, but
+/// clicking one activates the app, so a focus shortly after a notification of
+/// the window's profile opens what it was about, once.
+#[cfg(windows)]
+pub(crate) fn on_focus(window: &Window) {
+    let profile = window.state::<Profiles>().window_profile(window.label());
+    let shown = window.state::<Notifications>().shown.lock().unwrap().remove(&profile);
+    if let Some((target, at)) = shown {
+        if at.elapsed() < CLICK_WINDOW {
+            info!(profile, navigate = ?target, "opening notification");
+            telemetry::emit_to(window.app_handle(), window.label(), "navigate", target);
+        }
+    }
+}
+
+/// Whether a window showing `profile` has focus.
+fn profile_focused(app: &AppHandle, profile: &str) -> bool {
+    let profiles = app.state::<Profiles>();
+    app.webview_windows().values().any(|window| {
+        window.is_focused().unwrap_or(false) && profiles.window_profile(window.label()) == profile
+    })
+}
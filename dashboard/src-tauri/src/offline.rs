@@ -0,0 +1,670 @@
+use rusqlite::types::FromSql;
+use rusqlite::{params, Connection, OpenFlags, OptionalExtension, Row};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::{debug, warn};
+
+use crate::api::{AgentQuery, ApiError, FindingQuery, TaskQuery};
+use crate::models::{
+    AgentData, AgentFinding, AgentProgress, AgentStatus, FindingType, Handover, PhaseData, PhaseRecord, PhaseStatus,
+    Priority, ReviewData, ReviewStatus, Severity, TaskContext, TaskDetail, TaskStatus, TaskSummary, TrackedFiles,
+    Verdict,
+};
+use crate::workspace;
+
+/// How long a read waits for the orchestrator to finish writing
+const BUSY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Agent statuses counted as active, as in the orchestrator
+const ACTIVE_AGENT_STATUSES: &str = "('running', 'working', 'blocked', 'reviewing')";
+
+/// Agent statuses counted as failed
+const FAILED_AGENT_STATUSES: &str = "('failed', 'error', 'terminated', 'killed')";
+
+/// Default and maximum number of tasks listed, as in the API
+const DEFAULT_TASK_LIMIT: u32 = 200;
+const MAX_TASK_LIMIT: u32 = 1_000;
+
+/// Default number of findings listed, as in the API
+const DEFAULT_FINDING_LIMIT: u32 = 100;
+
+impl From<rusqlite::Error> for ApiError {
+    fn from(e: rusqlite::Error) -> Self {
+        Self::Database { message: e.to_string() }
+    }
+}
+
+/// Read-only view of the `registry/state.sqlite3` of every workspace base,
+/// serving the data commands while the backend is down.
+pub(crate) struct OfflineStore {
+    bases: Vec<PathBuf>,
+}
+
+impl OfflineStore {
+    /// Open the state databases of the workspace bases `bases`, skipping any
+    /// that has none.
+    pub(crate) fn open(bases: &[PathBuf]) -> Result<Self, ApiError> {
+        let bases: Vec<PathBuf> = bases
+            .iter()
+            .filter_map(|base| match workspace::validate(base) {
+                Ok(base) => Some(base),
+                Err(e) => {
+                    debug!(error = %e, "skipping workspace base");
+                    None
+                }
+            })
+            .collect();
+        if bases.is_empty() {
+            return Err(ApiError::Unavailable {
+                message: "No workspace with a state database was found".to_string(),
+            });
+        }
+        Ok(Self { bases })
+    }
+
+    /// Run `read` on a fresh read-only connection to the state database of
+    /// `base`, off the async runtime.
+    async fn read<T, F>(base: &Path, read: F) -> Result<T, ApiError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Connection) -> Result<T, ApiError> + Send + 'static,
+    {
+        let path = base.join("registry").join("state.sqlite3");
+        tauri::async_runtime::spawn_blocking(move || {
+            let conn = Connection::open_with_flags(
+                &path,
+                OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+            )?;
+            conn.busy_timeout(BUSY_TIMEOUT)?;
+            read(&conn)
+        })
+        .await
+        .map_err(|e| ApiError::Database { message: e.to_string() })?
+    }
+
+    /// Run `read` on the state database holding task `task_id`.
+    async fn read_task<T, F>(&self, task_id: &str, read: F) -> Result<T, ApiError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Connection) -> Result<T, ApiError> + Send + 'static,
+    {
+        for base in &self.bases {
+            let id = task_id.to_string();
+            match Self::read(base, move |conn| require_task(conn, &id)).await {
+                Ok(()) => return Self::read(base, read).await,
+                Err(ApiError::Status { status: 404, .. }) => {}
+                Err(e) => debug!(base = %base.display(), error = %e, "skipping unreadable state database"),
+            }
+        }
+        Err(not_found(&format!("Task {}", task_id)))
+    }
+
+    /// Tasks of every workspace base, newest first.
+    pub(crate) async fn tasks(&self, query: &TaskQuery) -> Result<Vec<TaskSummary>, ApiError> {
+        let offset = query.offset.unwrap_or(0) as usize;
+        let limit = query.limit.unwrap_or(DEFAULT_TASK_LIMIT).min(MAX_TASK_LIMIT) as usize;
+        // Each base's first `offset + limit` tasks cover the page of the merged list
+        let per_base = TaskQuery {
+            offset: Some(0),
+            limit: Some((offset + limit) as u32),
+            ..query.clone()
+        };
+
+        let mut merged = Vec::new();
+        for base in &self.bases {
+            let query = per_base.clone();
+            match Self::read(base, move |conn| tasks(conn, &query)).await {
+                Ok(tasks) => merged.extend(tasks),
+                Err(e) if self.bases.len() > 1 => {
+                    warn!(base = %base.display(), error = %e, "skipping unreadable state database")
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        merged.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(merged.into_iter().skip(offset).take(limit).collect())
+    }
+
+    pub(crate) async fn task(&self, task_id: &str) -> Result<TaskDetail, ApiError> {
+        let id = task_id.to_string();
+        self.read_task(task_id, move |conn| task(conn, &id)).await
+    }
+
+    /// The task's `AGENT_REGISTRY.json`, read from its workspace.
+    pub(crate) async fn task_registry(&self, task_id: &str) -> Result<Value, ApiError> {
+        let id = task_id.to_string();
+        let workspace = self
+            .read_task(task_id, move |conn| {
+                conn.query_row("SELECT workspace FROM tasks WHERE task_id = ?1", [&id], |row| {
+                    row.get::<_, Option<String>>(0)
+                })
+                .optional()?
+                .flatten()
+                .ok_or_else(|| not_found(&format!("Task {}", id)))
+            })
+            .await?;
+
+        let path = Path::new(&workspace).join("AGENT_REGISTRY.json");
+        let content = tokio::fs::read_to_string(&path)
+            .await
+            .map_err(|_| not_found("Task registry"))?;
+        serde_json::from_str(&content).map_err(|e| ApiError::Decode {
+            message: format!("Invalid registry {}: {}", path.display(), e),
+        })
+    }
+
+    pub(crate) async fn agents(&self, task_id: &str, query: &AgentQuery) -> Result<Vec<AgentData>, ApiError> {
+        let id = task_id.to_string();
+        let query = query.clone();
+        self.read_task(task_id, move |conn| {
+            Ok(agents(conn, &id)?
+                .into_iter()
+                .filter(|agent| query.phase_index.is_none() || query.phase_index == Some(agent.phase_index))
+                .filter(|agent| match query.status.as_deref() {
+                    Some(status) => parse::<AgentStatus>(&status.to_lowercase()) == Some(agent.status),
+                    None => true,
+                })
+                .collect())
+        })
+        .await
+    }
+
+    pub(crate) async fn phases(&self, task_id: &str) -> Result<Vec<PhaseRecord>, ApiError> {
+        let id = task_id.to_string();
+        self.read_task(task_id, move |conn| phases(conn, &id)).await
+    }
+
+    /// Latest progress of an agent; the database keeps only the last update.
+    pub(crate) async fn agent_progress(&self, task_id: &str, agent_id: &str) -> Result<Vec<AgentProgress>, ApiError> {
+        let (id, agent_id) = (task_id.to_string(), agent_id.to_string());
+        self.read_task(task_id, move |conn| {
+            let mut statement = conn.prepare(
+                "SELECT timestamp, status, progress, message FROM agent_progress_latest
+                 WHERE task_id = ?1 AND agent_id = ?2",
+            )?;
+            let rows = statement.query_map(params![id, agent_id], |row| {
+                Ok(AgentProgress {
+                    timestamp: row.get::<_, Option<String>>("timestamp")?.unwrap_or_default(),
+                    agent_id: agent_id.clone(),
+                    status: row.get::<_, Option<String>>("status")?.unwrap_or_default(),
+                    message: row.get::<_, Option<String>>("message")?.unwrap_or_default(),
+                    progress: percent(row.get("progress")?),
+                })
+            })?;
+            Ok(rows.collect::<Result<_, _>>()?)
+        })
+        .await
+    }
+
+    pub(crate) async fn agent_findings(
+        &self,
+        task_id: &str,
+        agent_id: &str,
+        query: &FindingQuery,
+    ) -> Result<Vec<AgentFinding>, ApiError> {
+        let (id, agent_id) = (task_id.to_string(), agent_id.to_string());
+        let query = query.clone();
+        self.read_task(task_id, move |conn| {
+            let mut statement = conn.prepare(
+                "SELECT agent_id, finding_type, severity, message, data, created_at FROM agent_findings
+                 WHERE task_id = ?1 AND agent_id = ?2
+                   AND (?3 IS NULL OR severity = ?3) AND (?4 IS NULL OR finding_type = ?4)
+                 ORDER BY created_at DESC LIMIT ?5",
+            )?;
+            let limit = query.limit.unwrap_or(DEFAULT_FINDING_LIMIT);
+            let rows = statement.query_map(
+                params![id, agent_id, query.severity, query.finding_type, limit],
+                |row| {
+                    Ok(AgentFinding {
+                        timestamp: row.get::<_, Option<String>>("created_at")?.unwrap_or_default(),
+                        agent_id: row.get("agent_id")?,
+                        finding_type: parse_column(row, "finding_type")?.unwrap_or(FindingType::Insight),
+                        severity: parse_column(row, "severity")?.unwrap_or(Severity::Low),
+                        message: row.get::<_, Option<String>>("message")?.unwrap_or_default(),
+                        data: json_column::<Value>(row, "data")?.and_then(|data| match data {
+                            Value::Object(map) => Some(map),
+                            _ => None,
+                        }),
+                    })
+                },
+            )?;
+            Ok(rows.collect::<Result<_, _>>()?)
+        })
+        .await
+    }
+
+    pub(crate) async fn handovers(&self, task_id: &str) -> Result<Vec<Handover>, ApiError> {
+        let id = task_id.to_string();
+        self.read_task(task_id, move |conn| {
+            let sql = "SELECT * FROM handovers WHERE task_id = ?1 ORDER BY from_phase_index ASC, created_at ASC";
+            let mut statement = match conn.prepare(sql) {
+                // Databases from before handovers have no such table
+                Err(e) if missing_table(&e) => return Ok(Vec::new()),
+                result => result?,
+            };
+            let rows = statement.query_map([&id], |row| {
+                Ok(Handover {
+                    handover_id: row.get("handover_id")?,
+                    task_id: row.get("task_id")?,
+                    from_phase_index: row.get("from_phase_index")?,
+                    to_phase_index: row.get("to_phase_index")?,
+                    summary: row.get("summary")?,
+                    key_findings: json_column(row, "key_findings")?.unwrap_or_default(),
+                    blockers: json_column(row, "blockers")?.unwrap_or_default(),
+                    recommendations: json_column(row, "recommendations")?.unwrap_or_default(),
+                    created_at: row.get("created_at")?,
+                })
+            })?;
+            Ok(rows.collect::<Result<_, _>>()?)
+        })
+        .await
+    }
+}
+
+fn not_found(what: &str) -> ApiError {
+    ApiError::Status {
+        status: 404,
+        message: format!("{} not found", what),
+    }
+}
+
+fn require_task(conn: &Connection, task_id: &str) -> Result<(), ApiError> {
+    let exists = conn
+        .query_row("SELECT 1 FROM tasks WHERE task_id = ?1", [task_id], |_| Ok(()))
+        .optional()?;
+    exists.ok_or_else(|| not_found(&format!("Task {}", task_id)))
+}
+
+/// Parse a database string into one of the models' enums.
+fn parse<T: DeserializeOwned>(value: &str) -> Option<T> {
+    serde_json::from_value(Value::String(value.to_string())).ok()
+}
+
+fn parse_column<T: DeserializeOwned>(row: &Row, column: &str) -> rusqlite::Result<Option<T>> {
+    Ok(row.get::<_, Option<String>>(column)?.and_then(|value| parse(&value)))
+}
+
+/// A column holding JSON text, `None` if empty or invalid.
+fn json_column<T: DeserializeOwned>(row: &Row, column: &str) -> rusqlite::Result<Option<T>> {
+    Ok(row
+        .get::<_, Option<String>>(column)?
+        .and_then(|text| serde_json::from_str(&text).ok()))
+}
+
+/// A column added by a later migration, `None` in databases without it.
+fn migrated_column<T: FromSql>(row: &Row, column: &str) -> rusqlite::Result<Option<T>> {
+    match row.get(column) {
+        Err(rusqlite::Error::InvalidColumnName(_)) => Ok(None),
+        result => result,
+    }
+}
+
+/// Whether `e` is about a table added by a later migration.
+fn missing_table(e: &rusqlite::Error) -> bool {
+    matches!(e, rusqlite::Error::SqliteFailure(_, Some(message)) if message.starts_with("no such table"))
+}
+
+fn percent(value: Option<i64>) -> u8 {
+    value.unwrap_or(0).clamp(0, 100) as u8
+}
+
+fn task_status(value: Option<String>) -> TaskStatus {
+    value
+        .and_then(|status| parse(&status.to_uppercase()))
+        .unwrap_or(TaskStatus::Initialized)
+}
+
+fn phase_status(value: Option<String>) -> PhaseStatus {
+    value
+        .and_then(|status| parse(&status.to_uppercase()))
+        .unwrap_or(PhaseStatus::Pending)
+}
+
+/// Lower bound of `created_at` for the API's `since` filter, which defaults to today.
+pub(crate) fn since_bound(since: Option<&str>) -> Option<String> {
+    let today = chrono::Local::now().date_naive();
+    let date = match since.unwrap_or("today") {
+        "all" => return None,
+        "today" => today,
+        "yesterday" => today - chrono::Duration::days(1),
+        "week" => today - chrono::Duration::days(7),
+        other => return Some(other.to_string()),
+    };
+    Some(date.to_string())
+}
+
+/// Upper bound of `created_at`; a plain date includes the whole day.
+pub(crate) fn until_bound(until: Option<&str>) -> Option<String> {
+    until.map(|until| {
+        if until.len() == 10 {
+            format!("{}T23:59:59.999999", until)
+        } else {
+            until.to_string()
+        }
+    })
+}
+
+fn tasks(conn: &Connection, query: &TaskQuery) -> Result<Vec<TaskSummary>, ApiError> {
+    let sql = format!(
+        "SELECT t.task_id, t.description, t.status, t.created_at, t.current_phase_index,
+                COUNT(DISTINCT a.agent_id) AS total_agents,
+                SUM(CASE WHEN a.status IN {active} THEN 1 ELSE 0 END) AS active_agents,
+                SUM(CASE WHEN a.status = 'completed' THEN 1 ELSE 0 END) AS completed_agents,
+                SUM(CASE WHEN a.status IN {failed} THEN 1 ELSE 0 END) AS failed_agents,
+                p.phase_id, p.name AS phase_name, p.status AS phase_status, p.created_at AS phase_created_at,
+                p.started_at AS phase_started_at, p.completed_at AS phase_completed_at
+           FROM tasks t
+           LEFT JOIN agents a ON a.task_id = t.task_id
+           LEFT JOIN phases p ON p.task_id = t.task_id AND p.phase_index = t.current_phase_index
+          WHERE (?1 IS NULL OR t.status = ?1)
+            AND (?2 IS NULL OR t.created_at >= ?2)
+            AND (?3 IS NULL OR t.created_at <= ?3)
+            AND (?4 IS NULL OR t.client_cwd LIKE '%' || ?4 || '%' OR t.workspace LIKE '%' || ?4 || '%')
+          GROUP BY t.task_id
+          ORDER BY t.created_at DESC
+          LIMIT ?5 OFFSET ?6",
+        active = ACTIVE_AGENT_STATUSES,
+        failed = FAILED_AGENT_STATUSES,
+    );
+    let mut statement = conn.prepare(&sql)?;
+    let rows = statement.query_map(
+        params![
+            query.status,
+            since_bound(query.since.as_deref()),
+            until_bound(query.until.as_deref()),
+            query.project,
+            query.limit.unwrap_or(DEFAULT_TASK_LIMIT).min(MAX_TASK_LIMIT),
+            query.offset.unwrap_or(0),
+        ],
+        |row| {
+            let created_at: String = row.get::<_, Option<String>>("created_at")?.unwrap_or_default();
+            let phase_index: i64 = row.get::<_, Option<i64>>("current_phase_index")?.unwrap_or(0);
+            let current_phase = match row.get::<_, Option<String>>("phase_name")? {
+                Some(name) => Some(PhaseData {
+                    id: row
+                        .get::<_, Option<String>>("phase_id")?
+                        .unwrap_or_else(|| format!("phase_{}", phase_index)),
+                    order: phase_index,
+                    name,
+                    description: None,
+                    status: phase_status(row.get("phase_status")?),
+                    created_at: row
+                        .get::<_, Option<String>>("phase_created_at")?
+                        .unwrap_or_else(|| created_at.clone()),
+                    started_at: row.get("phase_started_at")?,
+                    completed_at: row.get("phase_completed_at")?,
+                }),
+                None => None,
+            };
+            let total: i64 = row.get("total_agents")?;
+            let completed: i64 = row.get::<_, Option<i64>>("completed_agents")?.unwrap_or(0);
+            Ok(TaskSummary {
+                task_id: row.get("task_id")?,
+                description: row.get::<_, Option<String>>("description")?.unwrap_or_default(),
+                created_at,
+                status: task_status(row.get("status")?),
+                current_phase,
+                agent_count: total,
+                active_agents: row.get::<_, Option<i64>>("active_agents")?.unwrap_or(0),
+                failed_agents: row.get::<_, Option<i64>>("failed_agents")?.unwrap_or(0),
+                progress: if total > 0 { percent(Some(completed * 100 / total)) } else { 0 },
+            })
+        },
+    )?;
+    Ok(rows.collect::<Result<_, _>>()?)
+}
+
+fn task(conn: &Connection, task_id: &str) -> Result<TaskDetail, ApiError> {
+    let row = conn
+        .query_row(
+            "SELECT task_id, workspace, workspace_base, description, status, priority, client_cwd,
+                    created_at, current_phase_index
+               FROM tasks WHERE task_id = ?1",
+            [task_id],
+            |row| {
+                Ok((
+                    row.get::<_, Option<String>>("workspace")?.unwrap_or_default(),
+                    row.get::<_, Option<String>>("workspace_base")?.unwrap_or_default(),
+                    row.get::<_, Option<String>>("description")?.unwrap_or_default(),
+                    task_status(row.get("status")?),
+                    parse_column(row, "priority")?.unwrap_or(Priority::P2),
+                    row.get::<_, Option<String>>("client_cwd")?.unwrap_or_default(),
+                    row.get::<_, Option<String>>("created_at")?.unwrap_or_default(),
+                    row.get::<_, Option<i64>>("current_phase_index")?.unwrap_or(0),
+                ))
+            },
+        )
+        .optional()?;
+    let Some((workspace, workspace_base, description, status, priority, client_cwd, created_at, phase_index)) = row
+    else {
+        return Err(not_found(&format!("Task {}", task_id)));
+    };
+
+    let phases = phases(conn, task_id)?
+        .into_iter()
+        .map(|phase| {
+            let order = phase.phase_index.unwrap_or(0);
+            PhaseData {
+                id: phase.phase_id.unwrap_or_else(|| format!("phase_{}", order)),
+                order,
+                name: phase.name,
+                description: phase.description,
+                status: phase_status(phase.status),
+                created_at: phase.created_at.unwrap_or_else(|| created_at.clone()),
+                started_at: phase.started_at,
+                completed_at: phase.completed_at,
+            }
+        })
+        .collect();
+
+    let agents = agents(conn, task_id)?;
+    let config = task_config(conn, task_id)?;
+    let mut agent_hierarchy: HashMap<String, Vec<String>> = HashMap::new();
+    for agent in &agents {
+        agent_hierarchy
+            .entry(agent.parent.clone())
+            .or_default()
+            .push(agent.id.clone());
+    }
+    let active_count = agents
+        .iter()
+        .filter(|a| {
+            matches!(
+                a.status,
+                AgentStatus::Running | AgentStatus::Working | AgentStatus::Blocked | AgentStatus::Reviewing
+            )
+        })
+        .count() as i64;
+    let completed_count = agents
+        .iter()
+        .filter(|a| matches!(a.status, AgentStatus::Completed | AgentStatus::PhaseCompleted))
+        .count() as i64;
+
+    Ok(TaskDetail {
+        task_id: task_id.to_string(),
+        task_description: description,
+        created_at,
+        workspace,
+        workspace_base,
+        client_cwd,
+        status,
+        priority,
+        phases,
+        current_phase_index: phase_index,
+        total_spawned: agents.len() as i64,
+        active_count,
+        completed_count,
+        agents,
+        agent_hierarchy,
+        max_agents: config.max_agents,
+        max_depth: config.max_depth,
+        max_concurrent: config.max_concurrent,
+        reviews: reviews(conn, task_id)?,
+        task_context: config.context,
+    })
+}
+
+/// Limits and context of a task, from `task_config`.
+struct TaskConfig {
+    max_agents: i64,
+    max_depth: i64,
+    max_concurrent: i64,
+    context: TaskContext,
+}
+
+impl Default for TaskConfig {
+    /// The orchestrator's defaults, for tasks created before `task_config` existed
+    fn default() -> Self {
+        Self {
+            max_agents: 50,
+            max_depth: 5,
+            max_concurrent: 20,
+            context: TaskContext::default(),
+        }
+    }
+}
+
+fn task_config(conn: &Connection, task_id: &str) -> Result<TaskConfig, ApiError> {
+    let config = conn
+        .query_row(
+            "SELECT max_agents, max_depth, max_concurrent, expected_deliverables, success_criteria, relevant_files
+               FROM task_config WHERE task_id = ?1",
+            [task_id],
+            |row| {
+                let defaults = TaskConfig::default();
+                Ok(TaskConfig {
+                    max_agents: row.get::<_, Option<i64>>("max_agents")?.unwrap_or(defaults.max_agents),
+                    max_depth: row.get::<_, Option<i64>>("max_depth")?.unwrap_or(defaults.max_depth),
+                    max_concurrent: row
+                        .get::<_, Option<i64>>("max_concurrent")?
+                        .unwrap_or(defaults.max_concurrent),
+                    context: TaskContext {
+                        expected_deliverables: json_column(row, "expected_deliverables")?,
+                        success_criteria: json_column(row, "success_criteria")?,
+                        relevant_files: json_column(row, "relevant_files")?,
+                    },
+                })
+            },
+        )
+        .optional()?;
+    Ok(config.unwrap_or_default())
+}
+
+fn phases(conn: &Connection, task_id: &str) -> Result<Vec<PhaseRecord>, ApiError> {
+    let mut statement = conn.prepare(
+        "SELECT * FROM phases WHERE task_id = ?1 ORDER BY phase_index ASC",
+    )?;
+    let rows = statement.query_map([task_id], |row| {
+        Ok(PhaseRecord {
+            task_id: row.get("task_id")?,
+            phase_index: row.get("phase_index")?,
+            phase_id: row.get("phase_id")?,
+            name: row.get::<_, Option<String>>("name")?.unwrap_or_default(),
+            description: row.get("description")?,
+            deliverables: json_column(row, "deliverables")?.unwrap_or_default(),
+            success_criteria: json_column(row, "success_criteria")?.unwrap_or_default(),
+            status: row.get("status")?,
+            created_at: row.get("created_at")?,
+            started_at: row.get("started_at")?,
+            completed_at: row.get("completed_at")?,
+            revision_round: migrated_column(row, "revision_round")?,
+            max_revision_rounds: migrated_column(row, "max_revision_rounds")?,
+            last_review_id: migrated_column(row, "last_review_id")?,
+            revision_ready: migrated_column(row, "revision_ready")?,
+        })
+    })?;
+    Ok(rows.collect::<Result<_, _>>()?)
+}
+
+fn agents(conn: &Connection, task_id: &str) -> Result<Vec<AgentData>, ApiError> {
+    let mut statement = conn.prepare(
+        "SELECT agent_id, type, tmux_session, parent, depth, phase_index, status, started_at, completed_at,
+                progress, last_update, prompt_preview, claude_pid, cursor_pid, tracked_files
+           FROM agents WHERE task_id = ?1 ORDER BY started_at ASC",
+    )?;
+    let rows = statement.query_map([task_id], |row| {
+        let started_at = row.get::<_, Option<String>>("started_at")?.unwrap_or_default();
+        Ok(AgentData {
+            id: row.get("agent_id")?,
+            kind: row.get::<_, Option<String>>("type")?.unwrap_or_default(),
+            tmux_session: row.get::<_, Option<String>>("tmux_session")?.unwrap_or_default(),
+            parent: row
+                .get::<_, Option<String>>("parent")?
+                .unwrap_or_else(|| "orchestrator".to_string()),
+            depth: row.get::<_, Option<i64>>("depth")?.unwrap_or(1),
+            phase_index: row.get::<_, Option<i64>>("phase_index")?.unwrap_or(0),
+            status: row
+                .get::<_, Option<String>>("status")?
+                .and_then(|status| parse(&status.to_lowercase()))
+                .unwrap_or(AgentStatus::Running),
+            completed_at: row.get("completed_at")?,
+            progress: percent(row.get("progress")?),
+            last_update: row
+                .get::<_, Option<String>>("last_update")?
+                .unwrap_or_else(|| started_at.clone()),
+            started_at,
+            prompt: row.get::<_, Option<String>>("prompt_preview")?.unwrap_or_default(),
+            claude_pid: row.get("claude_pid")?,
+            cursor_pid: row.get("cursor_pid")?,
+            tracked_files: json_column::<TrackedFiles>(row, "tracked_files")?.unwrap_or_default(),
+        })
+    })?;
+    Ok(rows.collect::<Result<_, _>>()?)
+}
+
+fn reviews(conn: &Connection, task_id: &str) -> Result<Vec<ReviewData>, ApiError> {
+    let mut statement = conn.prepare(
+        "SELECT r.review_id, r.phase_index, r.status, r.verdict, r.created_at, r.num_reviewers,
+                (SELECT COUNT(*) FROM review_verdicts v WHERE v.review_id = r.review_id) AS verdicts_submitted
+           FROM reviews r WHERE r.task_id = ?1 ORDER BY r.created_at ASC",
+    )?;
+    let rows = statement.query_map([task_id], |row| {
+        Ok(ReviewData {
+            review_id: row.get("review_id")?,
+            phase_index: row.get("phase_index")?,
+            status: parse_column(row, "status")?.unwrap_or(ReviewStatus::Pending),
+            started_at: row.get::<_, Option<String>>("created_at")?.unwrap_or_default(),
+            reviewer_count: row.get::<_, Option<i64>>("num_reviewers")?.unwrap_or(0),
+            verdicts_submitted: row.get("verdicts_submitted")?,
+            // `mixed` and other aggregate verdicts have no API equivalent
+            final_verdict: parse_column::<Verdict>(row, "verdict")?,
+        })
+    })?;
+    Ok(rows.collect::<Result<_, _>>()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A workspace base whose state database predates handovers.
+    fn workspace_without_handovers() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("registry")).unwrap();
+        let conn = Connection::open(dir.path().join("registry").join("state.sqlite3")).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE tasks (task_id TEXT PRIMARY KEY, description TEXT, status TEXT, created_at TEXT);
+             INSERT INTO tasks VALUES ('TASK-1', 'Old task', 'ACTIVE', '2026-01-01T00:00:00');",
+        )
+        .unwrap();
+        dir
+    }
+
+    #[tokio::test]
+    async fn handovers_are_empty_without_their_table() {
+        let dir = workspace_without_handovers();
+        let store = OfflineStore::open(&[dir.path().to_path_buf()]).unwrap();
+        assert!(store.handovers("TASK-1").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn handovers_of_an_unknown_task_are_not_found() {
+        let dir = workspace_without_handovers();
+        let store = OfflineStore::open(&[dir.path().to_path_buf()]).unwrap();
+        let result = store.handovers("TASK-2").await;
+        assert!(matches!(result, Err(ApiError::Status { status: 404, .. })), "{:?}", result);
+    }
+}
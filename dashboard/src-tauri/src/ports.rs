@@ -0,0 +1,74 @@
+use std::net::{Ipv4Addr, TcpListener};
+use tracing::{debug, warn};
+
+/// Default preferred backend port - using 8765 to avoid conflicts with dev server on 8000
+pub(crate) const DEFAULT_BACKEND_PORT: u16 = 8765;
+
+/// Environment variable overriding the preferred backend port
+pub(crate) const PORT_ENV: &str = "CLAUDE_ORCHESTRATOR_BACKEND_PORT";
+
+/// Preferred backend port from the environment, then `configured`, falling back to the default.
+pub(crate) fn preferred_port(configured: Option<u16>) -> u16 {
+    let fallback = configured.filter(|port| *port != 0).unwrap_or(DEFAULT_BACKEND_PORT);
+    match std::env::var(PORT_ENV) {
+        Ok(value) => match value.trim().parse::<u16>() {
+            Ok(port) if port != 0 => port,
+            _ => {
+                warn!(env = PORT_ENV, %value, default = fallback, "ignoring invalid port");
+                fallback
+            }
+        },
+        Err(_) => fallback,
+    }
+}
+
+/// Whether nothing is listening on `port`, on either loopback or all interfaces.
+pub(crate) fn is_port_free(port: u16) -> bool {
+    TcpListener::bind((Ipv4Addr::LOCALHOST, port)).is_ok()
+        && TcpListener::bind((Ipv4Addr::UNSPECIFIED, port)).is_ok()
+}
+
+/// Ask the OS for a currently unused ephemeral port.
+pub(crate) fn ephemeral_port() -> std::io::Result<u16> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+    Ok(listener.local_addr()?.port())
+}
+
+/// Pick the first free port from `candidates`, or an ephemeral one.
+///
+/// The port is only probed, not reserved, so the sidecar may still lose a
+/// race for it - the supervisor's restart picks a new port in that case.
+pub(crate) fn choose_port(candidates: &[u16]) -> std::io::Result<u16> {
+    for &port in candidates.iter().filter(|port| **port != 0) {
+        if is_port_free(port) {
+            return Ok(port);
+        }
+        debug!(port, "port is in use, trying next");
+    }
+    ephemeral_port()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_free_candidate_is_chosen() {
+        let taken = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let taken_port = taken.local_addr().unwrap().port();
+        let free_port = ephemeral_port().unwrap();
+
+        assert!(!is_port_free(taken_port));
+        assert_eq!(choose_port(&[taken_port, free_port]).unwrap(), free_port);
+    }
+
+    #[test]
+    fn falls_back_to_an_ephemeral_port() {
+        let taken = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let taken_port = taken.local_addr().unwrap().port();
+
+        let port = choose_port(&[0, taken_port]).unwrap();
+        assert!(port != 0 && port != taken_port);
+        assert_ne!(choose_port(&[]).unwrap(), 0);
+    }
+}
@@ -0,0 +1,431 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
+use tracing::{info, warn};
+
+use crate::bridge::{self, Bridge};
+use crate::health::{local_url, ProbeConfig};
+use crate::logs::{BackendLogs, DEFAULT_LOG_CAPACITY};
+use crate::ports;
+use crate::search;
+use crate::settings::{BackendMode, Settings};
+use crate::sidecar::{Sidecar, SHUTDOWN_GRACE};
+use crate::state::{self, BackendState, BackendStateStore};
+use crate::supervisor::{self, RestartPolicy};
+use crate::telemetry;
+use crate::workspace::Workspace;
+
+/// Profile made from the top-level settings when no profiles are configured
+pub(crate) const DEFAULT_PROFILE: &str = "default";
+
+/// Label prefix of windows opened for a profile
+const PROFILE_WINDOW_PREFIX: &str = "profile-";
+
+/// A named workspace with its own backend, from the `profiles` section of the settings file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProfileConfig {
+    pub name: String,
+    /// Workspace base passed to the profile's sidecar
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<PathBuf>,
+    /// Port to try first for the profile's sidecar, any free port if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    /// Attach to this backend instead of spawning a sidecar
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backend_url: Option<String>,
+}
+
+/// Runtime of an active profile: its backend and everything observed about it.
+pub(crate) struct Backend {
+    pub profile: String,
+    pub mode: BackendMode,
+    pub probe: ProbeConfig,
+    pub sidecar: Sidecar,
+    pub state: BackendStateStore,
+    pub logs: BackendLogs,
+    pub workspace: Workspace,
+    /// Connection to the backend's `/ws` shared by the profile's windows
+    pub bridge: Bridge,
+}
+
+impl Backend {
+    /// Base URL the frontend should use for this backend.
+    pub(crate) fn url(&self) -> String {
+        match &self.mode {
+            BackendMode::External(url) => url.clone(),
+            BackendMode::Sidecar => local_url(self.sidecar.port()),
+        }
+    }
+}
+
+/// Entry of `list_profiles`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProfileInfo {
+    #[serde(flatten)]
+    pub config: ProfileConfig,
+    /// Started at launch and shown in new windows
+    pub is_default: bool,
+    /// Shown by the window asking
+    pub current: bool,
+    /// State of the profile's backend, if it is active
+    pub backend: Option<BackendState>,
+}
+
+/// `profile-changed` payload, sent to a window switched to another profile.
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProfileChangedPayload {
+    profile: String,
+    url: String,
+}
+
+/// Managed state: configured profiles, the backends of active ones and the
+/// profile each window shows.
+pub(crate) struct Profiles {
+    configs: Mutex<Vec<ProfileConfig>>,
+    /// The only profile was made from the top-level settings
+    implicit: bool,
+    default: Mutex<String>,
+    /// Default profile at launch, the one CLI and environment overrides apply to
+    launch_profile: String,
+    probe: ProbeConfig,
+    log_lines: usize,
+    active: Mutex<HashMap<String, Arc<Backend>>>,
+    /// Profile shown by each window label; other windows show the default
+    windows: Mutex<HashMap<String, String>>,
+}
+
+impl Profiles {
+    pub(crate) fn from_settings(settings: &Settings) -> Self {
+        let implicit = settings.profiles.is_empty();
+        let configs = if implicit {
+            vec![ProfileConfig {
+                name: DEFAULT_PROFILE.to_string(),
+                workspace: settings.workspace.clone(),
+                port: None,
+                backend_url: settings.backend_url.clone(),
+            }]
+        } else {
+            let mut configs: Vec<ProfileConfig> = Vec::new();
+            for config in &settings.profiles {
+                if config.name.trim().is_empty() || configs.iter().any(|c| c.name == config.name) {
+                    warn!(profile = %config.name, "ignoring profile without a unique name");
+                    continue;
+                }
+                configs.push(config.clone());
+            }
+            configs
+        };
+
+        let default = settings
+            .default_profile
+            .clone()
+            .filter(|name| configs.iter().any(|c| &c.name == name))
+            .or_else(|| configs.first().map(|c| c.name.clone()))
+            .unwrap_or_else(|| DEFAULT_PROFILE.to_string());
+
+        Self {
+            configs: Mutex::new(configs),
+            implicit,
+            launch_profile: default.clone(),
+            default: Mutex::new(default),
+            probe: settings.probe.clone(),
+            log_lines: settings.backend_log_lines.unwrap_or(DEFAULT_LOG_CAPACITY),
+            active: Mutex::new(HashMap::new()),
+            windows: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn default_profile(&self) -> String {
+        self.default.lock().unwrap().clone()
+    }
+
+    /// Backend of `profile`, if the profile is active.
+    pub(crate) fn backend(&self, profile: &str) -> Option<Arc<Backend>> {
+        self.active.lock().unwrap().get(profile).cloned()
+    }
+
+    /// Profile shown by the window labelled `label`.
+    pub(crate) fn window_profile(&self, label: &str) -> String {
+        self.windows
+            .lock()
+            .unwrap()
+            .get(label)
+            .cloned()
+            .unwrap_or_else(|| self.default_profile())
+    }
+
+    /// Backend of the profile shown by the window labelled `label`.
+    pub(crate) fn for_window(&self, label: &str) -> Result<Arc<Backend>, String> {
+        let profile = self.window_profile(label);
+        self.backend(&profile)
+            .ok_or_else(|| format!("Profile {:?} is not active", profile))
+    }
+
+    pub(crate) fn forget_window(&self, label: &str) {
+        if let Ok(backend) = self.for_window(label) {
+            backend.bridge.forget_window(label);
+        }
+        self.windows.lock().unwrap().remove(label);
+    }
+
+    pub(crate) fn list(&self, label: &str) -> Vec<ProfileInfo> {
+        let default = self.default_profile();
+        let current = self.window_profile(label);
+        self.configs
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|config| ProfileInfo {
+                is_default: config.name == default,
+                current: config.name == current,
+                backend: self.backend(&config.name).map(|b| b.state.snapshot()),
+                config: config.clone(),
+            })
+            .collect()
+    }
+
+    /// Whether `pid` is the sidecar of one of our profiles.
+    pub(crate) fn owns_pid(&self, pid: u32) -> bool {
+        self.active
+            .lock()
+            .unwrap()
+            .values()
+            .any(|b| b.sidecar.pid() == Some(pid))
+    }
+
+    /// Start the backend of `name` unless it is already running.
+    pub(crate) fn activate(&self, app: &AppHandle, name: &str) -> Result<Arc<Backend>, String> {
+        let config = self
+            .configs
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|c| c.name == name)
+            .cloned()
+            .ok_or_else(|| format!("Unknown profile {:?}", name))?;
+
+        let mut active = self.active.lock().unwrap();
+        if let Some(backend) = active.get(name) {
+            return Ok(backend.clone());
+        }
+
+        // CLI and environment overrides apply to the launch profile only
+        let overrides = name == self.launch_profile;
+        let mode = if overrides {
+            BackendMode::resolve(config.backend_url.as_deref())
+        } else {
+            BackendMode::from_config(config.backend_url.as_deref())
+        };
+        let preferred = if overrides {
+            ports::preferred_port(config.port)
+        } else {
+            config.port.unwrap_or(0)
+        };
+        let taken = |port: u16| active.values().any(|b| b.sidecar.port() == port);
+        let preferred = if preferred == 0 || taken(preferred) {
+            ports::choose_port(&[]).unwrap_or(0)
+        } else {
+            preferred
+        };
+
+        let backend = Arc::new(Backend {
+            profile: config.name.clone(),
+            mode: mode.clone(),
+            probe: self.probe.clone(),
+            sidecar: Sidecar::new(preferred),
+            state: BackendStateStore::new(&config.name),
+            logs: BackendLogs::new(self.log_lines),
+            workspace: Workspace::resolve(config.workspace.as_deref(), overrides),
+            bridge: Bridge::default(),
+        });
+        active.insert(config.name.clone(), backend.clone());
+        drop(active);
+
+        info!(profile = %config.name, ?mode, "activating profile");
+        bridge::run(app.clone(), backend.clone());
+        search::run(app.clone(), backend.clone());
+        #[cfg(desktop)]
+        crate::watcher::run(app.clone(), backend.clone());
+        match mode {
+            // Backend is run by the user - only health-check it
+            BackendMode::External(_) => supervisor::attach(app.clone(), backend.clone()),
+            BackendMode::Sidecar => {
+                // Provisional port so URL commands are valid before the sidecar
+                // is running - the supervisor resolves conflicts before spawning
+                backend.sidecar.set_port(preferred);
+
+                // Spawn and supervise backend sidecar - restarts it after crashes
+                supervisor::start(app.clone(), backend.clone(), RestartPolicy::default());
+            }
+        }
+        Ok(backend)
+    }
+
+    /// Stop the backend of `name`. The default profile and profiles shown in a
+    /// window stay active.
+    pub(crate) async fn deactivate(&self, app: &AppHandle, name: &str) -> Result<(), String> {
+        if name == self.default_profile() {
+            return Err(format!("Profile {:?} is the default profile", name));
+        }
+        if self.windows.lock().unwrap().values().any(|profile| profile == name) {
+            return Err(format!("Profile {:?} is shown in a window", name));
+        }
+        let Some(backend) = self.active.lock().unwrap().remove(name) else {
+            return Ok(());
+        };
+
+        info!(profile = %name, "deactivating profile");
+        backend.sidecar.shutdown(SHUTDOWN_GRACE).await;
+        state::stopped(app, &backend);
+        Ok(())
+    }
+
+    /// Show profile `name` in the window labelled `label`, starting its backend if needed.
+    pub(crate) fn switch_window(&self, app: &AppHandle, label: &str, name: &str) -> Result<Arc<Backend>, String> {
+        let backend = self.activate(app, name)?;
+        let previous = self
+            .windows
+            .lock()
+            .unwrap()
+            .insert(label.to_string(), name.to_string());
+        // Subscriptions were made on the old profile's bridge
+        if let Some(old) = previous.filter(|old| old != name).and_then(|old| self.backend(&old)) {
+            old.bridge.forget_window(label);
+        }
+
+        telemetry::emit_to(
+            app,
+            label,
+            "profile-changed",
+            ProfileChangedPayload {
+                profile: name.to_string(),
+                url: backend.url(),
+            },
+        );
+        Ok(backend)
+    }
+
+    /// Open (or focus) a window showing profile `name`.
+    pub(crate) fn open_window(&self, app: &AppHandle, name: &str) -> Result<(), String> {
+        let label = window_label(name);
+        if let Some(window) = app.get_webview_window(&label) {
+            return window.set_focus().map_err(|e| e.to_string());
+        }
+
+        self.activate(app, name)?;
+        // Assigned before the page loads so its first commands see the profile
+        self.windows
+            .lock()
+            .unwrap()
+            .insert(label.clone(), name.to_string());
+
+        WebviewWindowBuilder::new(app, &label, WebviewUrl::default())
+            .title(format!("Claude Orchestrator Dashboard - {}", name))
+            .inner_size(1400.0, 900.0)
+            .min_inner_size(1000.0, 700.0)
+            .center()
+            .build()
+            .map(|_| ())
+            .map_err(|e| {
+                self.forget_window(&label);
+                format!("Failed to open window for profile {:?}: {}", name, e)
+            })
+    }
+
+    /// Make `name` the profile started at launch and shown in new windows.
+    pub(crate) fn set_default(&self, app: &AppHandle, name: &str) -> Result<(), String> {
+        if !self.configs.lock().unwrap().iter().any(|c| c.name == name) {
+            return Err(format!("Unknown profile {:?}", name));
+        }
+        if self.implicit {
+            return Ok(());
+        }
+        Settings::update(app, "defaultProfile", serde_json::json!(name))?;
+        *self.default.lock().unwrap() = name.to_string();
+        Ok(())
+    }
+
+    /// Persist `base` as the workspace of profile `name`.
+    pub(crate) fn save_workspace(&self, app: &AppHandle, name: &str, base: &Path) -> Result<(), String> {
+        let mut configs = self.configs.lock().unwrap();
+        let config = configs
+            .iter_mut()
+            .find(|c| c.name == name)
+            .ok_or_else(|| format!("Unknown profile {:?}", name))?;
+        config.workspace = Some(base.to_path_buf());
+
+        if self.implicit {
+            Settings::update(app, "workspace", serde_json::json!(base))?;
+        } else {
+            Settings::update(app, "profiles", serde_json::json!(*configs))?;
+        }
+        info!(profile = %name, workspace = %base.display(), "saved workspace");
+        Ok(())
+    }
+
+    /// Stop every backend, in parallel.
+    pub(crate) async fn shutdown(&self, app: &AppHandle) {
+        let backends: Vec<Arc<Backend>> = self.active.lock().unwrap().values().cloned().collect();
+        let stops: Vec<_> = backends
+            .into_iter()
+            .map(|backend| {
+                let app = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    backend.sidecar.shutdown(SHUTDOWN_GRACE).await;
+                    state::stopped(&app, &backend);
+                })
+            })
+            .collect();
+        for stop in stops {
+            let _ = stop.await;
+        }
+    }
+
+    /// Kill every sidecar without blocking. Safe to call from a panic hook.
+    pub(crate) fn kill_now(&self) {
+        if let Ok(active) = self.active.try_lock() {
+            for backend in active.values() {
+                backend.sidecar.kill_now();
+            }
+        }
+    }
+}
+
+/// Window label for a profile - labels only allow a limited character set, so
+/// a hash of the full name keeps e.g. `a b` and `a_b` apart.
+fn window_label(profile: &str) -> String {
+    let name: String = profile
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    // FNV-1a, stable across builds unlike `DefaultHasher`
+    let hash = profile.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{}{}-{:08x}", PROFILE_WINDOW_PREFIX, name, hash as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn window_labels_are_valid_and_distinct() {
+        let names = ["api", "a b", "a_b", "a/b", "a:b", "ünï", "üñï"];
+        let labels: Vec<String> = names.iter().map(|name| window_label(name)).collect();
+        for label in &labels {
+            assert!(label.starts_with(PROFILE_WINDOW_PREFIX));
+            assert!(label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'), "{}", label);
+        }
+        let distinct: HashSet<&String> = labels.iter().collect();
+        assert_eq!(distinct.len(), names.len(), "{:?}", labels);
+        assert_eq!(window_label("api"), window_label("api"));
+    }
+}
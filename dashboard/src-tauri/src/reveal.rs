@@ -0,0 +1,146 @@
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+use tauri_plugin_opener::OpenerExt;
+use tracing::info;
+
+use crate::api::ApiError;
+use crate::data::DataSource;
+use crate::editor::{self, EditorConfig};
+use crate::workspace::{self, AgentFile};
+
+/// Where `open_agent_file` shows a file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum OpenIn {
+    /// Select the file in the file manager
+    #[default]
+    FileManager,
+    /// Open the file in the configured editor, else its default app
+    Editor,
+}
+
+/// Directory of task `task_id`, which must lie inside one of `bases`.
+pub(crate) async fn task_dir(source: &DataSource, bases: &[PathBuf], task_id: &str) -> Result<PathBuf, ApiError> {
+    let task = source.task(task_id).await?;
+    Ok(inside(bases, Path::new(&task.workspace))?)
+}
+
+/// `file` of agent `agent_id`, which must exist inside one of `bases`.
+pub(crate) async fn agent_file(
+    source: &DataSource,
+    bases: &[PathBuf],
+    task_id: &str,
+    agent_id: &str,
+    file: AgentFile,
+) -> Result<PathBuf, ApiError> {
+    let path = workspace::agent_file(source, task_id, agent_id, file).await?;
+    Ok(inside(bases, &path)?)
+}
+
+/// Canonical form of `path`, refused unless it exists inside one of `bases`.
+/// Paths come from the backend or the state database, so symlinks and `..`
+/// are resolved before checking.
+fn inside(bases: &[PathBuf], path: &Path) -> Result<PathBuf, String> {
+    let resolved = path
+        .canonicalize()
+        .map_err(|e| format!("Cannot open {}: {}", path.display(), e))?;
+    let contained = bases
+        .iter()
+        .filter_map(|base| base.canonicalize().ok())
+        .any(|base| resolved.starts_with(base));
+    if contained {
+        Ok(resolved)
+    } else {
+        Err(format!("{} is outside the workspaces", path.display()))
+    }
+}
+
+/// Show a task directory in the file manager.
+pub(crate) fn open_dir(app: &AppHandle, dir: &Path) -> Result<(), String> {
+    app.opener()
+        .open_path(dir.to_string_lossy(), None::<&str>)
+        .map_err(|e| format!("Failed to open {}: {}", dir.display(), e))?;
+    info!(dir = %dir.display(), "opened task directory");
+    Ok(())
+}
+
+/// Show `path` in the file manager, or open it at `line` in the editor.
+pub(crate) fn open_file(
+    app: &AppHandle,
+    editor: &EditorConfig,
+    path: &Path,
+    open_in: OpenIn,
+    line: Option<u32>,
+) -> Result<(), String> {
+    match open_in {
+        OpenIn::Editor => editor::open(app, editor, path, line),
+        OpenIn::FileManager => {
+            app.opener()
+                .reveal_item_in_dir(path)
+                .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+            info!(path = %path.display(), "revealed agent file");
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn files_inside_a_base() {
+        let dir = tempfile::tempdir().unwrap();
+        let (one, two) = (dir.path().join("one"), dir.path().join("two"));
+        std::fs::create_dir_all(one.join("TASK-1").join("logs")).unwrap();
+        std::fs::create_dir_all(two.join("TASK-2")).unwrap();
+        let log = one.join("TASK-1").join("logs").join("a_stream.jsonl");
+        std::fs::write(&log, "").unwrap();
+
+        let bases = [one.clone(), two.clone()];
+        assert_eq!(inside(&bases, &log).unwrap(), log.canonicalize().unwrap());
+        assert!(inside(&bases, &two.join("TASK-2")).is_ok());
+    }
+
+    #[test]
+    fn dot_dot_cannot_escape() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path().join("base");
+        std::fs::create_dir_all(base.join("TASK-1")).unwrap();
+        std::fs::write(dir.path().join("secret"), "").unwrap();
+
+        let escape = base.join("TASK-1").join("..").join("..").join("secret");
+        assert!(inside(&[base], &escape).unwrap_err().contains("outside the workspaces"));
+    }
+
+    #[test]
+    fn sibling_with_a_common_prefix_is_outside() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path().join("base");
+        let sibling = dir.path().join("base-other");
+        std::fs::create_dir_all(&base).unwrap();
+        std::fs::create_dir_all(&sibling).unwrap();
+        assert!(inside(&[base], &sibling).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_cannot_escape() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path().join("base");
+        std::fs::create_dir_all(base.join("TASK-1")).unwrap();
+        std::fs::write(dir.path().join("secret"), "").unwrap();
+        let link = base.join("TASK-1").join("link");
+        std::os::unix::fs::symlink(dir.path().join("secret"), &link).unwrap();
+
+        assert!(inside(&[base], &link).is_err());
+    }
+
+    #[test]
+    fn missing_files_and_bases_are_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(inside(&[dir.path().to_path_buf()], &dir.path().join("missing")).is_err());
+        assert!(inside(&[dir.path().join("missing")], dir.path()).is_err());
+    }
+}
@@ -0,0 +1,711 @@
+use rusqlite::{params, Connection, OpenFlags, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tracing::{debug, info, info_span, warn, Instrument};
+
+use crate::api::ApiError;
+use crate::offline;
+use crate::profiles::Backend;
+use crate::tail::{self, StreamEntry};
+
+/// Bumped when the index layout changes; older indexes are rebuilt
+const SCHEMA_VERSION: i64 = 3;
+
+/// Delay between passes over the workspace
+const INDEX_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Lines indexed per transaction, so an interrupted pass resumes where it stopped
+const LINES_PER_COMMIT: usize = 2_000;
+
+/// How long a search waits for the orchestrator to finish writing the state database
+const STATE_DB_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Default and maximum number of hits returned
+const DEFAULT_LIMIT: u32 = 50;
+const MAX_LIMIT: u32 = 500;
+
+/// Marks around matched terms in snippets
+const HIGHLIGHT_START: &str = "\u{2}";
+const HIGHLIGHT_END: &str = "\u{3}";
+
+/// Directories of a task workspace holding indexed files
+const INDEXED_DIRS: &[&str] = &["logs", "progress", "findings", "archive"];
+
+/// Kinds of indexed files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum SourceKind {
+    /// `logs/<agent>_stream.jsonl`
+    StreamLog,
+    /// `progress/<agent>_progress.jsonl`
+    Progress,
+    /// `findings/<agent>_findings.jsonl`
+    Findings,
+}
+
+impl SourceKind {
+    const ALL: [Self; 3] = [Self::StreamLog, Self::Progress, Self::Findings];
+
+    fn suffix(self) -> &'static str {
+        match self {
+            Self::StreamLog => "_stream.jsonl",
+            Self::Progress => "_progress.jsonl",
+            Self::Findings => "_findings.jsonl",
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::StreamLog => "streamLog",
+            Self::Progress => "progress",
+            Self::Findings => "findings",
+        }
+    }
+
+    fn parse(kind: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|k| k.as_str() == kind)
+    }
+}
+
+/// Filters of a search.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct SearchFilters {
+    pub task_id: Option<String>,
+    pub agent_id: Option<String>,
+    pub phase_index: Option<i64>,
+    pub kind: Option<SourceKind>,
+    /// `YYYY-MM-DD`, an ISO timestamp, `today`, `yesterday` or `week`
+    pub since: Option<String>,
+    pub until: Option<String>,
+    pub limit: Option<u32>,
+}
+
+/// A line matching a search, best first.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SearchHit {
+    pub path: String,
+    pub kind: SourceKind,
+    pub task_id: String,
+    pub agent_id: String,
+    pub phase_index: Option<i64>,
+    /// 1-based line number
+    pub line: i64,
+    /// Byte offset of the line, e.g. to resume a log tail there
+    pub offset: i64,
+    pub timestamp: Option<String>,
+    /// Text around the match, matched terms between `\u0002` and `\u0003`
+    pub snippet: String,
+    /// Higher is more relevant
+    pub score: f64,
+}
+
+/// Keep the search indexes of the profile's workspace bases up to date until
+/// the profile is deactivated.
+pub(crate) fn run(app: AppHandle, backend: Arc<Backend>) {
+    let span = info_span!("search_index", profile = %backend.profile);
+    let task = async move {
+        let mut interval = tokio::time::interval(INDEX_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = backend.sidecar.shut_down() => break,
+            }
+            for base in backend.workspace.bases(&app) {
+                let index = match index_path(&app, &base) {
+                    Ok(index) => index,
+                    Err(e) => {
+                        warn!(error = %e, "no location for the search index");
+                        return;
+                    }
+                };
+                let pass = tauri::async_runtime::spawn_blocking(move || index_workspace(&index, &base));
+                match pass.await {
+                    Ok(Ok(0)) => {}
+                    Ok(Ok(lines)) => debug!(lines, "indexed new lines"),
+                    Ok(Err(e)) => warn!(error = %e, "indexing failed"),
+                    Err(e) => warn!(error = %e, "indexing failed"),
+                }
+            }
+        }
+        debug!("search indexer stopped");
+    };
+    tauri::async_runtime::spawn(task.instrument(span));
+}
+
+/// Search the indexes of the profile's workspace bases.
+pub(crate) async fn search(
+    app: &AppHandle,
+    backend: &Backend,
+    query: &str,
+    filters: SearchFilters,
+) -> Result<Vec<SearchHit>, ApiError> {
+    let Some(query) = match_expression(query) else {
+        return Ok(Vec::new());
+    };
+    let bases = backend.workspace.bases(app);
+    if bases.is_empty() {
+        return Err(ApiError::Unavailable {
+            message: "No workspace to search".to_string(),
+        });
+    }
+    let indexes = bases
+        .iter()
+        .map(|base| index_path(app, base))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|message| ApiError::Unavailable { message })?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut hits = Vec::new();
+        for (index, base) in indexes.iter().zip(&bases).filter(|(index, _)| index.is_file()) {
+            let conn = Connection::open_with_flags(
+                index,
+                OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+            )?;
+            let phases = attach_state_db(&conn, base);
+            hits.extend(query_index(&conn, &query, &filters, phases)?);
+        }
+        // Scores of the indexes are comparable, all being bm25 of the same query
+        hits.sort_by(|a: &SearchHit, b| b.score.total_cmp(&a.score));
+        hits.truncate(filters.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize);
+        Ok(hits)
+    })
+    .await
+    .map_err(|e| ApiError::Database { message: e.to_string() })?
+}
+
+/// FTS5 query matching every whitespace-separated term of `query` as a
+/// phrase, so `state_db.py` or `KeyError:` need no escaping.
+fn match_expression(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+/// Attach the state database of `base` as `state`, for the agents' current
+/// phases. Returns whether it could be.
+fn attach_state_db(conn: &Connection, base: &Path) -> bool {
+    let db = base.join("registry").join("state.sqlite3");
+    if !db.is_file() {
+        return false;
+    }
+    let attached = conn
+        .busy_timeout(STATE_DB_TIMEOUT)
+        .and_then(|()| conn.execute("ATTACH DATABASE ?1 AS state", [db.to_string_lossy()]));
+    match attached {
+        Ok(_) => true,
+        Err(e) => {
+            debug!(path = %db.display(), error = %e, "searching without phases");
+            false
+        }
+    }
+}
+
+/// Hits of `query` in the index `conn`. Agents move between phases after their
+/// lines are indexed, so with `phases` each hit gets its agent's current phase
+/// from the attached state database; without, hits have none.
+fn query_index(
+    conn: &Connection,
+    query: &str,
+    filters: &SearchFilters,
+    phases: bool,
+) -> Result<Vec<SearchHit>, ApiError> {
+    let phase = if phases {
+        "(SELECT phase_index FROM state.agents a WHERE a.task_id = entries.task_id AND a.agent_id = entries.agent_id)"
+    } else {
+        "NULL"
+    };
+    let sql = format!(
+        "SELECT path, kind, task_id, agent_id, {phase} AS phase_index, line, offset, timestamp,
+                snippet(entries, 0, ?2, ?3, '…', 24) AS snippet, bm25(entries) AS score
+           FROM entries
+          WHERE entries MATCH ?1
+            AND (?4 IS NULL OR task_id = ?4)
+            AND (?5 IS NULL OR agent_id = ?5)
+            AND (?6 IS NULL OR {phase} = ?6)
+            AND (?7 IS NULL OR kind = ?7)
+            AND (?8 IS NULL OR time >= ?8)
+            AND (?9 IS NULL OR time <= ?9)
+          ORDER BY score
+          LIMIT ?10",
+        phase = phase,
+    );
+    let mut statement = conn.prepare(&sql)?;
+    let since = time_bound("since", filters.since.as_deref().and_then(|since| offline::since_bound(Some(since))))?;
+    let until = time_bound("until", offline::until_bound(filters.until.as_deref()))?;
+    let rows = statement.query_map(
+        params![
+            query,
+            HIGHLIGHT_START,
+            HIGHLIGHT_END,
+            filters.task_id,
+            filters.agent_id,
+            filters.phase_index,
+            filters.kind.map(SourceKind::as_str),
+            since,
+            until,
+            filters.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT),
+        ],
+        |row| {
+            let kind: String = row.get("kind")?;
+            Ok(SearchHit {
+                path: row.get("path")?,
+                kind: SourceKind::parse(&kind).unwrap_or(SourceKind::StreamLog),
+                task_id: row.get("task_id")?,
+                agent_id: row.get("agent_id")?,
+                phase_index: row.get("phase_index")?,
+                line: row.get("line")?,
+                offset: row.get("offset")?,
+                timestamp: row.get("timestamp")?,
+                snippet: row.get("snippet")?,
+                score: -row.get::<_, f64>("score")?,
+            })
+        },
+    )?;
+    Ok(rows.collect::<Result<_, _>>()?)
+}
+
+/// Index of the workspace base `base`, kept with the app's data rather than in
+/// the orchestrator's workspace.
+fn index_path(app: &AppHandle, base: &Path) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_local_data_dir()
+        .map_err(|e| format!("Cannot locate the app data directory: {}", e))?;
+    // FNV-1a, stable across builds unlike `DefaultHasher`
+    let hash = base
+        .to_string_lossy()
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+        });
+    Ok(dir.join("search").join(format!("{:016x}.sqlite3", hash)))
+}
+
+fn open_index(path: &Path) -> Result<Connection, ApiError> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| ApiError::Database {
+            message: format!("Cannot create {}: {}", dir.display(), e),
+        })?;
+    }
+    let conn = Connection::open(path)?;
+    conn.pragma_update(None, "journal_mode", "WAL")?;
+    conn.pragma_update(None, "synchronous", "NORMAL")?;
+
+    let version: i64 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    if version != SCHEMA_VERSION {
+        info!(path = %path.display(), "creating search index");
+        conn.execute_batch(
+            "DROP TABLE IF EXISTS files;
+             DROP TABLE IF EXISTS entries;
+             CREATE TABLE files (
+               path TEXT PRIMARY KEY,
+               file_id INTEGER,
+               indexed_to INTEGER NOT NULL,
+               lines INTEGER NOT NULL
+             );
+             CREATE VIRTUAL TABLE entries USING fts5(
+               text,
+               path UNINDEXED, kind UNINDEXED, task_id UNINDEXED, agent_id UNINDEXED,
+               line UNINDEXED, offset UNINDEXED, timestamp UNINDEXED, time UNINDEXED
+             );",
+        )?;
+        conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+    }
+    Ok(conn)
+}
+
+/// A file of a task workspace to index.
+struct Source {
+    path: PathBuf,
+    kind: SourceKind,
+    task_id: String,
+    agent_id: String,
+}
+
+/// Index what was appended to the workspace's files since the last pass.
+/// Returns the number of lines indexed.
+fn index_workspace(index: &Path, base: &Path) -> Result<usize, ApiError> {
+    let mut conn = open_index(index)?;
+    let sources = sources(base);
+
+    let mut lines = 0;
+    for source in &sources {
+        match index_file(&mut conn, source) {
+            Ok(n) => lines += n,
+            Err(e) => debug!(path = %source.path.display(), error = %e, "skipping file"),
+        }
+    }
+
+    // Files removed or moved to the archive since
+    let present: HashSet<String> = sources.iter().map(|s| s.path.display().to_string()).collect();
+    let indexed: Vec<String> = conn
+        .prepare("SELECT path FROM files")?
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+    for path in indexed.iter().filter(|path| !present.contains(*path)) {
+        let tx = conn.transaction()?;
+        tx.execute("DELETE FROM entries WHERE path = ?1", [path])?;
+        tx.execute("DELETE FROM files WHERE path = ?1", [path])?;
+        tx.commit()?;
+    }
+    Ok(lines)
+}
+
+/// Stream logs, progress and findings files of every task workspace under `base`.
+fn sources(base: &Path) -> Vec<Source> {
+    let Ok(tasks) = std::fs::read_dir(base) else {
+        return Vec::new();
+    };
+    let mut sources = Vec::new();
+    for task in tasks.flatten() {
+        let task_id = task.file_name().to_string_lossy().into_owned();
+        if !task_id.starts_with("TASK-") {
+            continue;
+        }
+        for dir in INDEXED_DIRS {
+            let Ok(files) = std::fs::read_dir(task.path().join(dir)) else {
+                continue;
+            };
+            for file in files.flatten() {
+                let name = file.file_name().to_string_lossy().into_owned();
+                let Some((kind, agent_id)) = SourceKind::ALL
+                    .into_iter()
+                    .find_map(|kind| Some((kind, name.strip_suffix(kind.suffix())?)))
+                else {
+                    continue;
+                };
+                sources.push(Source {
+                    path: file.path(),
+                    kind,
+                    task_id: task_id.clone(),
+                    agent_id: agent_id.to_string(),
+                });
+            }
+        }
+    }
+    sources
+}
+
+/// Index the complete lines appended to `source` since it was last indexed,
+/// starting over if it was replaced or truncated.
+fn index_file(conn: &mut Connection, source: &Source) -> Result<usize, ApiError> {
+    let path = source.path.display().to_string();
+    let mut file = File::open(&source.path).map_err(|e| ApiError::Database { message: e.to_string() })?;
+    let metadata = file.metadata().map_err(|e| ApiError::Database { message: e.to_string() })?;
+    let file_id = tail::file_id(&metadata).map(|id| id as i64);
+    let size = metadata.len() as i64;
+    // Lines without a timestamp of their own are dated by the file's last write
+    let modified = metadata.modified().ok().map(chrono::DateTime::<chrono::Utc>::from);
+
+    let indexed = conn
+        .query_row(
+            "SELECT file_id, indexed_to, lines FROM files WHERE path = ?1",
+            [&path],
+            |row| Ok((row.get::<_, Option<i64>>(0)?, row.get::<_, i64>(1)?, row.get::<_, i64>(2)?)),
+        )
+        .optional()?;
+    let (mut offset, mut line) = match indexed {
+        Some((id, indexed_to, lines)) if id == file_id && indexed_to <= size => (indexed_to, lines),
+        Some(_) => {
+            conn.execute("DELETE FROM entries WHERE path = ?1", [&path])?;
+            (0, 0)
+        }
+        None => (0, 0),
+    };
+    if offset == size {
+        return Ok(0);
+    }
+
+    file.seek(SeekFrom::Start(offset as u64))
+        .map_err(|e| ApiError::Database { message: e.to_string() })?;
+    let mut reader = BufReader::new(file);
+    let mut buffer = Vec::new();
+    let mut total = 0;
+    loop {
+        let tx = conn.transaction()?;
+        let mut batch = 0;
+        while batch < LINES_PER_COMMIT {
+            buffer.clear();
+            let n = reader
+                .read_until(b'\n', &mut buffer)
+                .map_err(|e| ApiError::Database { message: e.to_string() })?;
+            // A line without its newline is still being written
+            if n == 0 || buffer.last() != Some(&b'\n') {
+                break;
+            }
+            line += 1;
+            if let Some((text, timestamp)) = extract(source.kind, offset as u64, &buffer) {
+                let time = match timestamp.as_deref() {
+                    Some(timestamp) => epoch_seconds(timestamp),
+                    None => modified.map(|modified| modified.timestamp()),
+                };
+                let timestamp = timestamp.or_else(|| modified.map(|modified| modified.to_rfc3339()));
+                tx.execute(
+                    "INSERT INTO entries (text, path, kind, task_id, agent_id, line, offset, timestamp, time)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                    params![
+                        text,
+                        path,
+                        source.kind.as_str(),
+                        source.task_id,
+                        source.agent_id,
+                        line,
+                        offset,
+                        timestamp,
+                        time,
+                    ],
+                )?;
+            }
+            offset += n as i64;
+            batch += 1;
+        }
+        tx.execute(
+            "INSERT INTO files (path, file_id, indexed_to, lines) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(path) DO UPDATE SET file_id = ?2, indexed_to = ?3, lines = ?4",
+            params![path, file_id, offset, line],
+        )?;
+        tx.commit()?;
+        total += batch;
+        if batch < LINES_PER_COMMIT {
+            return Ok(total);
+        }
+    }
+}
+
+/// Searchable text of a line, and its own timestamp if it has one.
+fn extract(kind: SourceKind, offset: u64, line: &[u8]) -> Option<(String, Option<String>)> {
+    let value: Value = serde_json::from_slice(line).ok()?;
+    let timestamp = value.get("timestamp").and_then(Value::as_str).map(str::to_string);
+    let field = |name: &str| value.get(name).and_then(Value::as_str).unwrap_or_default();
+
+    let text = match kind {
+        SourceKind::Progress => format!("{} {}", field("status"), field("message")),
+        SourceKind::Findings => {
+            let data = value.get("data").filter(|data| !data.is_null());
+            format!(
+                "{} {} {} {}",
+                field("finding_type"),
+                field("severity"),
+                field("message"),
+                data.map(Value::to_string).unwrap_or_default()
+            )
+        }
+        SourceKind::StreamLog => {
+            let mut entries = Vec::new();
+            // Indexed in full, unlike the log viewer's cut-off tool results
+            tail::parse_line(offset, line, None, &mut entries);
+            entries
+                .into_iter()
+                .filter_map(|entry| match entry {
+                    StreamEntry::AssistantText { text, .. } => Some(text),
+                    StreamEntry::ToolUse { name, input, .. } => Some(format!("{} {}", name, input)),
+                    StreamEntry::ToolResult { content, .. } => Some(content),
+                    StreamEntry::Error { message, .. } => Some(message),
+                    StreamEntry::Other { .. } => None,
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+    };
+    if text.trim().is_empty() {
+        return None;
+    }
+    Some((text, timestamp))
+}
+
+/// UTC epoch seconds of an ISO 8601 timestamp or date. Those without an
+/// offset are local time, as the orchestrator writes them.
+fn epoch_seconds(timestamp: &str) -> Option<i64> {
+    if let Ok(time) = chrono::DateTime::parse_from_rfc3339(timestamp) {
+        return Some(time.timestamp());
+    }
+    let naive = chrono::NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%dT%H:%M:%S%.f")
+        .or_else(|_| chrono::NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M:%S%.f"))
+        .or_else(|_| {
+            chrono::NaiveDate::parse_from_str(timestamp, "%Y-%m-%d").map(|date| date.and_time(chrono::NaiveTime::MIN))
+        })
+        .ok()?;
+    naive
+        .and_local_timezone(chrono::Local)
+        .earliest()
+        .map(|time| time.timestamp())
+}
+
+/// Epoch seconds of a `since` or `until` filter, already turned into a bound.
+fn time_bound(name: &str, bound: Option<String>) -> Result<Option<i64>, ApiError> {
+    bound
+        .map(|bound| {
+            epoch_seconds(&bound).ok_or_else(|| ApiError::Status {
+                status: 400,
+                message: format!("Invalid {} {:?}, expected a date or an ISO timestamp", name, bound),
+            })
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn terms_are_quoted_phrases() {
+        assert_eq!(match_expression("  state_db.py  KeyError: "), Some("\"state_db.py\" \"KeyError:\"".to_string()));
+        assert_eq!(match_expression("say \"hi\""), Some("\"say\" \"\"\"hi\"\"\"".to_string()));
+        assert_eq!(match_expression(" \t"), None);
+    }
+
+    #[test]
+    fn progress_and_findings_text() {
+        let line = br#"{"timestamp":"2026-01-04T15:00:00","status":"working","message":"Reading state_db"}"#;
+        assert_eq!(
+            extract(SourceKind::Progress, 0, line),
+            Some(("working Reading state_db".to_string(), Some("2026-01-04T15:00:00".to_string())))
+        );
+
+        let line = br#"{"finding_type":"issue","severity":"high","message":"KeyError","data":{"file":"a.py"}}"#;
+        assert_eq!(
+            extract(SourceKind::Findings, 0, line),
+            Some((r#"issue high KeyError {"file":"a.py"}"#.to_string(), None))
+        );
+    }
+
+    #[test]
+    fn stream_log_tool_results_are_indexed_in_full() {
+        let content = "x".repeat(20_000);
+        let line = serde_json::json!({
+            "type": "user",
+            "message": {"content": [{"type": "tool_result", "tool_use_id": "t1", "content": content}]},
+        })
+        .to_string();
+        let (text, _) = extract(SourceKind::StreamLog, 0, line.as_bytes()).unwrap();
+        assert_eq!(text.len(), 20_000);
+    }
+
+    #[test]
+    fn lines_without_text_are_skipped() {
+        assert_eq!(extract(SourceKind::StreamLog, 0, br#"{"type":"system","subtype":"init"}"#), None);
+        assert_eq!(extract(SourceKind::Progress, 0, b"not json"), None);
+    }
+
+    #[test]
+    fn timestamps_with_and_without_offset() {
+        assert_eq!(epoch_seconds("2026-01-04T15:00:00+00:00"), Some(1_767_538_800));
+        assert_eq!(epoch_seconds("2026-01-04T16:00:00.5+01:00"), Some(1_767_538_800));
+
+        let local = chrono::NaiveDate::from_ymd_opt(2026, 1, 4)
+            .unwrap()
+            .and_hms_opt(15, 0, 0)
+            .unwrap()
+            .and_local_timezone(chrono::Local)
+            .unwrap()
+            .timestamp();
+        assert_eq!(epoch_seconds("2026-01-04T15:00:00.123456"), Some(local));
+        assert_eq!(epoch_seconds("2026-01-04 15:00:00"), Some(local));
+        assert_eq!(epoch_seconds("2026-01-04"), Some(local - 15 * 3600));
+        assert_eq!(epoch_seconds("yesterday"), None);
+    }
+
+    #[test]
+    fn invalid_time_bound_is_a_bad_request() {
+        assert!(matches!(
+            time_bound("since", Some("soon".to_string())),
+            Err(ApiError::Status { status: 400, .. })
+        ));
+        assert_eq!(time_bound("since", None).unwrap(), None);
+    }
+
+    #[test]
+    fn index_and_query() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path().join("workspaces");
+        let progress = base.join("TASK-1").join("progress");
+        std::fs::create_dir_all(&progress).unwrap();
+        std::fs::write(
+            progress.join("fixer-1_progress.jsonl"),
+            concat!(
+                r#"{"timestamp":"2026-01-04T10:00:00+00:00","status":"working","message":"KeyError in state_db"}"#,
+                "\n",
+                r#"{"timestamp":"2026-01-06T10:00:00+00:00","status":"completed","message":"KeyError fixed"}"#,
+                "\n",
+                r#"{"status":"working","message":"KeyError partial"#,
+            ),
+        )
+        .unwrap();
+
+        let index = dir.path().join("index.sqlite3");
+        assert_eq!(index_workspace(&index, &base).unwrap(), 2);
+        assert_eq!(index_workspace(&index, &base).unwrap(), 0);
+
+        let conn = open_index(&index).unwrap();
+        let query = match_expression("KeyError").unwrap();
+        let hits = query_index(&conn, &query, &SearchFilters::default(), false).unwrap();
+        assert_eq!(hits.len(), 2);
+        assert!(hits.iter().all(|hit| hit.task_id == "TASK-1" && hit.agent_id == "fixer-1"));
+
+        let filters = SearchFilters {
+            since: Some("2026-01-05T00:00:00+00:00".to_string()),
+            ..Default::default()
+        };
+        let hits = query_index(&conn, &query, &filters, false).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!((hits[0].line, hits[0].kind), (2, SourceKind::Progress));
+        assert!(hits[0].snippet.contains("\u{2}KeyError\u{3}"));
+
+        let filters = SearchFilters {
+            until: Some("2026-01-05T00:00:00+00:00".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(query_index(&conn, &query, &filters, false).unwrap()[0].line, 1);
+    }
+
+    #[test]
+    fn phases_are_read_when_searching() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path().join("workspaces");
+        for task_id in ["TASK-1", "TASK-2"] {
+            let progress = base.join(task_id).join("progress");
+            std::fs::create_dir_all(&progress).unwrap();
+            std::fs::write(progress.join("fixer-1_progress.jsonl"), "{\"message\":\"KeyError\"}\n").unwrap();
+        }
+        std::fs::create_dir_all(base.join("registry")).unwrap();
+        let state = Connection::open(base.join("registry").join("state.sqlite3")).unwrap();
+        state
+            .execute_batch(
+                "CREATE TABLE agents (agent_id TEXT, task_id TEXT, phase_index INTEGER);
+                 INSERT INTO agents VALUES ('fixer-1', 'TASK-1', 0), ('fixer-1', 'TASK-2', 2);",
+            )
+            .unwrap();
+
+        let index = dir.path().join("index.sqlite3");
+        assert_eq!(index_workspace(&index, &base).unwrap(), 2);
+        let conn = open_index(&index).unwrap();
+        assert!(attach_state_db(&conn, &base));
+        let query = match_expression("KeyError").unwrap();
+        let in_phase = |phase_index| {
+            let filters = SearchFilters {
+                phase_index: Some(phase_index),
+                ..Default::default()
+            };
+            let hits = query_index(&conn, &query, &filters, true).unwrap();
+            hits.into_iter().map(|hit| (hit.task_id, hit.phase_index)).collect::<Vec<_>>()
+        };
+        assert_eq!(in_phase(0), [("TASK-1".to_string(), Some(0))]);
+
+        // Moving on takes effect without indexing the lines again
+        state.execute("UPDATE agents SET phase_index = 1 WHERE task_id = 'TASK-1'", []).unwrap();
+        assert!(in_phase(0).is_empty());
+        assert_eq!(in_phase(1), [("TASK-1".to_string(), Some(1))]);
+        assert_eq!(in_phase(2), [("TASK-2".to_string(), Some(2))]);
+    }
+}
@@ -0,0 +1,243 @@
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+use tracing::warn;
+
+use crate::editor::EditorConfig;
+use crate::health::ProbeConfig;
+use crate::launcher::ExternalTerminalConfig;
+use crate::logfile::LogFileConfig;
+use crate::notifications::NotificationConfig;
+use crate::profiles::ProfileConfig;
+use crate::telemetry::LoggingConfig;
+
+/// Environment variable pointing the app at an already running backend
+pub(crate) const BACKEND_URL_ENV: &str = "CLAUDE_ORCHESTRATOR_BACKEND_URL";
+
+/// Command line flag pointing the app at an already running backend
+const BACKEND_URL_FLAG: &str = "--backend-url";
+
+const SETTINGS_FILE: &str = "settings.json";
+
+/// Held across each read-modify-write of the settings file, so concurrent
+/// updates of different keys don't drop each other
+static UPDATE: Mutex<()> = Mutex::new(());
+
+/// User settings persisted in the app config directory.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct Settings {
+    /// Attach to this backend instead of spawning the sidecar
+    pub backend_url: Option<String>,
+    /// Readiness and liveness probing
+    pub probe: ProbeConfig,
+    /// Sidecar output lines kept in memory for the backend console
+    pub backend_log_lines: Option<usize>,
+    /// Rotation of the log files in the platform log directory
+    pub log_files: LogFileConfig,
+    /// Log level and format of the desktop core
+    pub logging: LoggingConfig,
+    /// Orchestration events raised as native notifications
+    pub notifications: NotificationConfig,
+    /// Terminal emulator `open_agent_terminal` opens agent sessions in
+    pub external_terminal: ExternalTerminalConfig,
+    /// Editor `open_agent_file` opens files in
+    pub editor: EditorConfig,
+    /// Workspace base passed to the sidecar, chosen with `set_workspace`
+    pub workspace: Option<PathBuf>,
+    /// Named workspaces with their own backends; without any, the settings
+    /// above form a single `default` profile
+    pub profiles: Vec<ProfileConfig>,
+    /// Profile started at launch and shown in new windows
+    pub default_profile: Option<String>,
+}
+
+impl Settings {
+    pub(crate) fn path(app: &AppHandle) -> Option<PathBuf> {
+        app.path()
+            .app_config_dir()
+            .ok()
+            .map(|dir| dir.join(SETTINGS_FILE))
+    }
+
+    /// Read the settings file. A missing file gives the defaults.
+    pub(crate) fn read(app: &AppHandle) -> Result<Self, String> {
+        let Some(path) = Self::path(app) else {
+            return Ok(Self::default());
+        };
+        match std::fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|e| format!("Invalid settings file {}: {}", path.display(), e)),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    /// Set one top-level key in the settings file, keeping everything else as written.
+    pub(crate) fn update(app: &AppHandle, key: &str, value: serde_json::Value) -> Result<(), String> {
+        let path = Self::path(app).ok_or("No config directory available")?;
+        update_file(&path, key, value)
+    }
+}
+
+fn update_file(path: &Path, key: &str, value: serde_json::Value) -> Result<(), String> {
+    let _update = UPDATE.lock().unwrap();
+    let mut file = match std::fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid settings file {}: {}", path.display(), e))?,
+        Err(_) => serde_json::Value::Object(Default::default()),
+    };
+    let object = file
+        .as_object_mut()
+        .ok_or_else(|| format!("Settings file {} is not a JSON object", path.display()))?;
+    object.insert(key.to_string(), value);
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+    let content = serde_json::to_string_pretty(&file).map_err(|e| e.to_string())?;
+    // Replaced in one step, so a crash mid-write can't leave a truncated file
+    let temp = path.with_extension("json.tmp");
+    let written = std::fs::File::create(&temp)
+        .and_then(|mut file| file.write_all(content.as_bytes()).and_then(|()| file.sync_all()))
+        .and_then(|()| std::fs::rename(&temp, path));
+    written.map_err(|e| {
+        let _ = std::fs::remove_file(&temp);
+        format!("Failed to write {}: {}", path.display(), e)
+    })
+}
+
+/// Where the backend comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum BackendMode {
+    /// Spawn and supervise the bundled `dashboard-api` sidecar
+    Sidecar,
+    /// Attach to a backend the user runs themselves, e.g. `uvicorn --reload`
+    External(String),
+}
+
+impl BackendMode {
+    /// Resolve the mode: CLI flag, then environment, then the profile's configuration.
+    pub(crate) fn resolve(configured: Option<&str>) -> Self {
+        let url = cli_backend_url().or_else(|| std::env::var(BACKEND_URL_ENV).ok());
+        Self::from_config(url.as_deref().or(configured))
+    }
+
+    /// Mode for a configured backend URL, ignoring CLI and environment.
+    pub(crate) fn from_config(url: Option<&str>) -> Self {
+        match url.map(str::trim).filter(|url| !url.is_empty()) {
+            None => Self::Sidecar,
+            Some(url) => match normalize_url(url) {
+                Some(url) => Self::External(url),
+                None => {
+                    warn!(url, "ignoring backend URL that isn't an http(s) origin, spawning sidecar");
+                    Self::Sidecar
+                }
+            },
+        }
+    }
+}
+
+/// Value of `--backend-url <url>` or `--backend-url=<url>`.
+fn cli_backend_url() -> Option<String> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == BACKEND_URL_FLAG {
+            return args.next();
+        }
+        if let Some(value) = arg.strip_prefix(BACKEND_URL_FLAG).and_then(|v| v.strip_prefix('=')) {
+            return Some(value.to_string());
+        }
+    }
+    None
+}
+
+/// Accept only http(s) origins, without a trailing slash. Requests append the
+/// backend's own `/api/...` and `/ws` paths, so a base path would be doubled.
+fn normalize_url(url: &str) -> Option<String> {
+    let url = url.trim_end_matches('/');
+    let rest = url
+        .strip_prefix("http://")
+        .or_else(|| url.strip_prefix("https://"))?;
+    (!rest.is_empty() && !rest.contains(['/', '?', '#'])).then(|| url.to_string())
+}
+
+/// WebSocket base URL for an http(s) backend URL.
+pub(crate) fn ws_url(http_url: &str) -> String {
+    if let Some(rest) = http_url.strip_prefix("https://") {
+        format!("wss://{}", rest)
+    } else if let Some(rest) = http_url.strip_prefix("http://") {
+        format!("ws://{}", rest)
+    } else {
+        http_url.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn http_urls_lose_their_trailing_slashes() {
+        assert_eq!(normalize_url("http://127.0.0.1:8765/").as_deref(), Some("http://127.0.0.1:8765"));
+        assert_eq!(normalize_url("https://host//").as_deref(), Some("https://host"));
+    }
+
+    #[test]
+    fn other_urls_are_rejected() {
+        for url in ["", "http://", "https:///", "ws://host", "ftp://host", "host:8765", "HTTP://host"] {
+            assert_eq!(normalize_url(url), None, "{:?} should be rejected", url);
+        }
+    }
+
+    #[test]
+    fn base_paths_are_rejected() {
+        for url in ["https://host/api", "http://127.0.0.1:8765/api/", "http://host?x=1", "http://host#api"] {
+            assert_eq!(normalize_url(url), None, "{:?} should be rejected", url);
+        }
+    }
+
+    #[test]
+    fn update_keeps_other_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config").join(SETTINGS_FILE);
+        update_file(&path, "backendUrl", serde_json::json!("http://localhost:8765")).unwrap();
+        update_file(&path, "defaultProfile", serde_json::json!("dev")).unwrap();
+
+        let file: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(file, serde_json::json!({"backendUrl": "http://localhost:8765", "defaultProfile": "dev"}));
+        assert!(!path.with_extension("json.tmp").exists());
+    }
+
+    #[test]
+    fn concurrent_updates_are_all_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(SETTINGS_FILE);
+        std::thread::scope(|scope| {
+            for i in 0..8 {
+                let path = &path;
+                scope.spawn(move || update_file(path, &format!("key{}", i), serde_json::json!(i)).unwrap());
+            }
+        });
+
+        let file: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(file.as_object().unwrap().len(), 8);
+    }
+
+    #[test]
+    fn update_refuses_an_invalid_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(SETTINGS_FILE);
+        std::fs::write(&path, "[1, 2]").unwrap();
+        assert!(update_file(&path, "key", serde_json::json!(1)).is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "[1, 2]");
+    }
+
+    #[test]
+    fn websocket_scheme_follows_http_scheme() {
+        assert_eq!(ws_url("http://127.0.0.1:8765"), "ws://127.0.0.1:8765");
+        assert_eq!(ws_url("https://host/api"), "wss://host/api");
+        assert_eq!(ws_url("ws://host"), "ws://host");
+    }
+}
@@ -0,0 +1,388 @@
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU16, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::async_runtime::Receiver;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_shell::process::{CommandChild, CommandEvent};
+use tauri_plugin_shell::ShellExt;
+use tokio::sync::Notify;
+use tracing::{debug, error, info, instrument, warn};
+
+use crate::conflict::stop_process;
+use crate::logs::{self, LogStream};
+use crate::profiles::Backend;
+use crate::state;
+use crate::telemetry::SIDECAR_TARGET;
+use crate::workspace::WORKSPACE_ENV;
+
+/// How long the sidecar gets to exit after SIGTERM before it is killed
+pub(crate) const SHUTDOWN_GRACE: Duration = Duration::from_secs(5);
+
+/// Directory under the app's local data dir with one file per running sidecar
+const PID_DIR: &str = "sidecars";
+
+/// A profile's sidecar process.
+#[derive(Default)]
+pub(crate) struct Sidecar {
+    child: Mutex<Option<CommandChild>>,
+    /// Port the backend is (or will be) listening on, 0 until chosen
+    port: AtomicU16,
+    /// Port to try first, 0 for any free port
+    preferred_port: u16,
+    /// Pid of a stale backend adopted instead of spawning one
+    adopted: Mutex<Option<u32>>,
+    shutting_down: AtomicBool,
+    shutdown: Notify,
+    /// Set by `restart` so the supervisor relaunches without counting a crash
+    restart_requested: AtomicBool,
+    /// Signalled by `restart`, to cut short the supervisor's backoff
+    restart: Notify,
+    exited: Notify,
+    /// Pid file recording the running sidecar, see `record_pid`
+    pid_file: Mutex<Option<PathBuf>>,
+}
+
+impl Sidecar {
+    pub(crate) fn new(preferred_port: u16) -> Self {
+        Self {
+            preferred_port,
+            ..Self::default()
+        }
+    }
+
+    /// Port the backend is using.
+    pub(crate) fn port(&self) -> u16 {
+        self.port.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn set_port(&self, port: u16) {
+        let previous = self.port.swap(port, Ordering::SeqCst);
+        if previous != 0 && previous != port {
+            info!(previous, port, "backend port changed");
+        }
+    }
+
+    /// Port to try first, 0 for any free port.
+    pub(crate) fn preferred_port(&self) -> u16 {
+        self.preferred_port
+    }
+
+    /// Port to try for the next launch: the one already in use so URLs stay
+    /// stable across restarts, else the configured preferred port (0 if none).
+    pub(crate) fn wanted_port(&self) -> u16 {
+        match self.port() {
+            0 => self.preferred_port,
+            port => port,
+        }
+    }
+
+    /// Pid of the running sidecar process.
+    pub(crate) fn pid(&self) -> Option<u32> {
+        self.child.lock().unwrap().as_ref().map(CommandChild::pid)
+    }
+
+    /// Take ownership of a stale backend from a previous run.
+    pub(crate) fn adopt(&self, app: &AppHandle, pid: Option<u32>) {
+        *self.adopted.lock().unwrap() = pid;
+        *self.pid_file.lock().unwrap() = pid.and_then(|pid| pid_file(app, pid));
+    }
+
+    /// Forget an adopted backend once it has gone away.
+    pub(crate) fn release_adopted(&self) {
+        if self.adopted.lock().unwrap().take().is_some() {
+            self.forget_pid();
+        }
+    }
+
+    /// Whether the app is shutting the sidecar down (no restarts wanted).
+    pub(crate) fn is_shutting_down(&self) -> bool {
+        self.shutting_down.load(Ordering::SeqCst)
+    }
+
+    fn store(&self, child: CommandChild) {
+        // Shutdown raced with a (re)spawn - don't leave an orphan behind
+        if self.is_shutting_down() {
+            let _ = child.kill();
+            return;
+        }
+        *self.child.lock().unwrap() = Some(child);
+    }
+
+    fn mark_exited(&self) {
+        self.child.lock().unwrap().take();
+        self.forget_pid();
+        self.exited.notify_waiters();
+    }
+
+    fn forget_pid(&self) {
+        if let Some(path) = self.pid_file.lock().unwrap().take() {
+            if std::fs::remove_file(&path).is_ok() {
+                debug!(path = %path.display(), "removed sidecar pid file");
+            }
+        }
+    }
+
+    /// Resolve once `shutdown` has been called.
+    pub(crate) async fn shut_down(&self) {
+        let notified = self.shutdown.notified();
+        tokio::pin!(notified);
+        notified.as_mut().enable();
+        if self.is_shutting_down() {
+            return;
+        }
+        notified.await;
+    }
+
+    /// Resolve once `restart` has been called, unless the request was already taken.
+    pub(crate) async fn requested_restart(&self) {
+        let notified = self.restart.notified();
+        tokio::pin!(notified);
+        notified.as_mut().enable();
+        if self.restart_requested.load(Ordering::SeqCst) {
+            return;
+        }
+        notified.await;
+    }
+
+    /// Whether the last exit was requested by `restart`, clearing the request.
+    pub(crate) fn take_restart_request(&self) -> bool {
+        self.restart_requested.swap(false, Ordering::SeqCst)
+    }
+
+    /// Stop the sidecar for good: SIGTERM, wait up to `grace`, then SIGKILL.
+    pub(crate) async fn shutdown(&self, grace: Duration) {
+        self.shutting_down.store(true, Ordering::SeqCst);
+        self.shutdown.notify_waiters();
+        self.stop(grace).await;
+    }
+
+    /// Stop the sidecar and have the supervisor launch it again right away.
+    pub(crate) async fn restart(&self, grace: Duration) {
+        self.restart_requested.store(true, Ordering::SeqCst);
+        self.restart.notify_waiters();
+        self.stop(grace).await;
+    }
+
+    async fn stop(&self, grace: Duration) {
+        // Register for the exit notification before signalling
+        let exited = self.exited.notified();
+        tokio::pin!(exited);
+        exited.as_mut().enable();
+
+        let adopted = self.adopted.lock().unwrap().take();
+        if let Some(pid) = adopted {
+            info!(pid, "stopping adopted backend");
+            stop_process(pid, self.port()).await;
+            self.forget_pid();
+        }
+
+        let Some(child) = self.child.lock().unwrap().take() else {
+            return;
+        };
+        let pid = child.pid();
+
+        if !terminate(pid) {
+            info!(pid, "killing sidecar");
+            let _ = child.kill();
+        } else {
+            info!(pid, grace_ms = grace.as_millis() as u64, "sent SIGTERM to sidecar");
+            if tokio::time::timeout(grace, exited).await.is_err() {
+                error!(pid, grace_ms = grace.as_millis() as u64, "sidecar did not exit in time, killing");
+                let _ = child.kill();
+            }
+        }
+        // The app may exit before the termination is reported
+        self.forget_pid();
+    }
+
+    /// Kill the sidecar immediately without blocking. Safe to call from a panic hook.
+    pub(crate) fn kill_now(&self) {
+        self.shutting_down.store(true, Ordering::SeqCst);
+        if let Ok(mut guard) = self.child.try_lock() {
+            if let Some(child) = guard.take() {
+                let _ = child.kill();
+            }
+        }
+        if let Ok(mut guard) = self.adopted.try_lock() {
+            if let Some(pid) = guard.take() {
+                force_kill(pid);
+            }
+        }
+    }
+}
+
+fn pid_file(app: &AppHandle, pid: u32) -> Option<PathBuf> {
+    let dir = app.path().app_local_data_dir().ok()?.join(PID_DIR);
+    Some(dir.join(format!("{}.pid", pid)))
+}
+
+/// Record a sidecar we spawned, so one left behind by a crashed run can be
+/// told apart from a backend the user started.
+fn record_pid(app: &AppHandle, pid: u32, port: u16) -> Option<PathBuf> {
+    let path = pid_file(app, pid)?;
+    let result = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|()| std::fs::write(&path, port.to_string()));
+    match result {
+        Ok(()) => Some(path),
+        Err(e) => {
+            warn!(pid, path = %path.display(), error = %e, "cannot record sidecar pid");
+            None
+        }
+    }
+}
+
+/// Whether `pid` was recorded as a sidecar spawned by this app and never seen
+/// exiting, i.e. one left behind by an earlier run.
+pub(crate) fn spawned_by_us(app: &AppHandle, pid: u32) -> bool {
+    pid_file(app, pid).is_some_and(|path| path.is_file())
+}
+
+/// Forget a stale sidecar once it has been stopped.
+pub(crate) fn forget_stale(app: &AppHandle, pid: u32) {
+    if let Some(path) = pid_file(app, pid) {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+/// Ask the process to exit gracefully. Returns false where unsupported.
+#[cfg(unix)]
+pub(crate) fn terminate(pid: u32) -> bool {
+    // SAFETY: kill(2) has no memory-safety preconditions
+    unsafe { libc::kill(pid as libc::pid_t, libc::SIGTERM) == 0 }
+}
+
+#[cfg(windows)]
+pub(crate) fn terminate(_pid: u32) -> bool {
+    false
+}
+
+/// Kill the process outright.
+#[cfg(unix)]
+pub(crate) fn force_kill(pid: u32) -> bool {
+    // SAFETY: kill(2) has no memory-safety preconditions
+    unsafe { libc::kill(pid as libc::pid_t, libc::SIGKILL) == 0 }
+}
+
+#[cfg(windows)]
+pub(crate) fn force_kill(pid: u32) -> bool {
+    std::process::Command::new("taskkill")
+        .args(["/PID", &pid.to_string(), "/F"])
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Spawn the profile's `dashboard-api` sidecar on `port` and return its event stream.
+#[instrument(skip(app, backend))]
+pub(crate) fn spawn_backend(
+    app: &AppHandle,
+    backend: &Backend,
+    port: u16,
+) -> Result<Receiver<CommandEvent>, String> {
+    let shell = app.shell();
+    let sidecar = &backend.sidecar;
+    sidecar.set_port(port);
+
+    // Create sidecar command - handle errors gracefully
+    let sidecar_cmd = match shell.sidecar("dashboard-api") {
+        Ok(cmd) => cmd,
+        Err(e) => {
+            error!(error = %e, "failed to create sidecar command");
+            return Err(format!("Failed to create sidecar: {}", e));
+        }
+    };
+
+    // Spawn with port argument, pointed at the chosen workspace if any
+    let mut sidecar_cmd = sidecar_cmd.args(["--port", &port.to_string()]);
+    if let Some(workspace) = backend.workspace.get() {
+        info!(workspace = %workspace.display(), "passing workspace to sidecar");
+        sidecar_cmd = sidecar_cmd.env(WORKSPACE_ENV, workspace);
+    }
+
+    let (rx, child) = match sidecar_cmd.spawn() {
+        Ok(result) => result,
+        Err(e) => {
+            error!(error = %e, "failed to spawn sidecar");
+            return Err(format!("Failed to spawn sidecar: {}", e));
+        }
+    };
+
+    info!(port, pid = child.pid(), "spawned dashboard-api sidecar");
+    *sidecar.pid_file.lock().unwrap() = record_pid(app, child.pid(), port);
+    sidecar.store(child);
+    Ok(rx)
+}
+
+/// Relay sidecar output until the process exits.
+///
+/// Returns the exit code, or `None` if the process was killed by a signal or
+/// the event channel closed without a `Terminated` event.
+pub(crate) async fn monitor_backend(
+    app: &AppHandle,
+    backend: &Backend,
+    mut rx: Receiver<CommandEvent>,
+    generation: u64,
+) -> Option<i32> {
+    while let Some(event) = rx.recv().await {
+        match event {
+            CommandEvent::Stdout(line) => {
+                let line_str = String::from_utf8_lossy(&line);
+                info!(target: SIDECAR_TARGET, stream = "stdout", "{}", line_str.trim_end());
+                logs::record(app, backend, LogStream::Stdout, &line_str);
+
+                // Detect when uvicorn is ready
+                if is_ready_line(&line_str) {
+                    state::ready(app, backend, generation);
+                }
+            }
+            CommandEvent::Stderr(line) => {
+                let line_str = String::from_utf8_lossy(&line);
+                info!(target: SIDECAR_TARGET, stream = "stderr", "{}", line_str.trim_end());
+                logs::record(app, backend, LogStream::Stderr, &line_str);
+
+                // Uvicorn logs to stderr
+                if is_ready_line(&line_str) {
+                    state::ready(app, backend, generation);
+                }
+            }
+            CommandEvent::Error(err) => {
+                error!(target: SIDECAR_TARGET, error = %err, "sidecar error");
+                logs::record(app, backend, LogStream::Stderr, &format!("ERROR: {}", err));
+            }
+            CommandEvent::Terminated(status) => {
+                warn!(code = ?status.code, signal = ?status.signal, "sidecar terminated");
+                backend.sidecar.mark_exited();
+                return status.code;
+            }
+            _ => {}
+        }
+    }
+
+    // Channel closed without a Terminated event - treat as a crash
+    backend.sidecar.mark_exited();
+    None
+}
+
+fn is_ready_line(line: &str) -> bool {
+    line.contains("Uvicorn running") || line.contains("Application startup complete")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn restart_wakes_the_supervisor() {
+        let sidecar = Sidecar::default();
+        let wait = tokio::time::timeout(Duration::from_secs(5), sidecar.requested_restart());
+        let (woken, ()) = tokio::join!(wait, sidecar.restart(Duration::ZERO));
+        assert!(woken.is_ok());
+
+        // A pending request resolves at once until it is taken
+        assert!(tokio::time::timeout(Duration::from_millis(10), sidecar.requested_restart()).await.is_ok());
+        assert!(sidecar.take_restart_request());
+        assert!(tokio::time::timeout(Duration::from_millis(10), sidecar.requested_restart()).await.is_err());
+    }
+}
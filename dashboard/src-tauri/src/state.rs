@@ -0,0 +1,196 @@
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::AppHandle;
+use tracing::info;
+
+use crate::profiles::Backend;
+use crate::telemetry;
+
+/// Lifecycle of the backend as seen by the desktop core.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum BackendStatus {
+    /// Not running and not supposed to be (before launch, after shutdown)
+    Stopped,
+    /// Launched, waiting for the first successful health check
+    Starting,
+    /// Healthy and serving requests
+    Ready,
+    /// Running but not passing health checks
+    Degraded,
+    /// Exited unexpectedly, a restart is scheduled
+    Restarting,
+    /// Gave up after repeated crashes
+    Failed,
+}
+
+/// Full backend state, emitted as `backend-state-changed` on every transition.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BackendState {
+    /// Profile the backend belongs to
+    pub profile: String,
+    pub status: BackendStatus,
+    /// Base URL of the backend being run or attached to
+    pub url: Option<String>,
+    /// Bumped on every launch; updates from an older launch are ignored
+    pub generation: u64,
+    pub restart_count: u32,
+    pub last_error: Option<String>,
+    /// Unix milliseconds of the last transition
+    pub changed_at: u64,
+    /// Unix milliseconds of the current launch
+    pub started_at: Option<u64>,
+    /// Unix milliseconds the current launch became ready
+    pub ready_at: Option<u64>,
+}
+
+impl BackendState {
+    fn new(profile: &str) -> Self {
+        Self {
+            profile: profile.to_string(),
+            status: BackendStatus::Stopped,
+            url: None,
+            generation: 0,
+            restart_count: 0,
+            last_error: None,
+            changed_at: now_ms(),
+            started_at: None,
+            ready_at: None,
+        }
+    }
+}
+
+/// Current `BackendState` of a profile's backend.
+pub(crate) struct BackendStateStore(Mutex<BackendState>);
+
+impl BackendStateStore {
+    pub(crate) fn new(profile: &str) -> Self {
+        Self(Mutex::new(BackendState::new(profile)))
+    }
+
+    pub(crate) fn snapshot(&self) -> BackendState {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Apply `update` and emit the new state if it reports a change.
+fn transition(
+    app: &AppHandle,
+    backend: &Backend,
+    update: impl FnOnce(&mut BackendState) -> bool,
+) -> BackendState {
+    let (changed, state) = {
+        let mut state = backend.state.0.lock().unwrap();
+        let changed = update(&mut state);
+        if changed {
+            state.changed_at = now_ms();
+        }
+        (changed, state.clone())
+    };
+
+    if changed {
+        info!(
+            profile = %state.profile,
+            status = ?state.status,
+            generation = state.generation,
+            restart_count = state.restart_count,
+            last_error = ?state.last_error,
+            "backend state changed"
+        );
+        telemetry::emit(app, "backend-state-changed", state.clone());
+    }
+    state
+}
+
+/// Whether launch `generation` is still the one being supervised.
+pub(crate) fn is_current(backend: &Backend, generation: u64) -> bool {
+    let state = backend.state.snapshot();
+    state.generation == generation
+        && !matches!(
+            state.status,
+            BackendStatus::Stopped | BackendStatus::Restarting | BackendStatus::Failed
+        )
+}
+
+/// A new launch (or attach) of the backend at `url`. Returns its generation.
+pub(crate) fn starting(app: &AppHandle, backend: &Backend, url: String) -> u64 {
+    transition(app, backend, |s| {
+        s.status = BackendStatus::Starting;
+        s.url = Some(url);
+        s.generation += 1;
+        s.started_at = Some(now_ms());
+        s.ready_at = None;
+        true
+    })
+    .generation
+}
+
+/// The launch `generation` passed a health check.
+pub(crate) fn ready(app: &AppHandle, backend: &Backend, generation: u64) {
+    transition(app, backend, |s| {
+        let current = s.generation == generation
+            && matches!(s.status, BackendStatus::Starting | BackendStatus::Degraded);
+        if current {
+            s.status = BackendStatus::Ready;
+            s.ready_at = Some(now_ms());
+            s.last_error = None;
+        }
+        current
+    });
+}
+
+/// The launch `generation` is up but failing health checks.
+pub(crate) fn degraded(app: &AppHandle, backend: &Backend, generation: u64, error: impl Into<String>) {
+    let error = error.into();
+    transition(app, backend, |s| {
+        let current = s.generation == generation
+            && matches!(s.status, BackendStatus::Starting | BackendStatus::Ready | BackendStatus::Degraded);
+        let changed = current
+            && (s.status != BackendStatus::Degraded || s.last_error.as_deref() != Some(error.as_str()));
+        if changed {
+            s.status = BackendStatus::Degraded;
+            s.last_error = Some(error);
+        }
+        changed
+    });
+}
+
+/// The backend exited and will be restarted.
+pub(crate) fn restarting(app: &AppHandle, backend: &Backend, error: String) {
+    transition(app, backend, |s| {
+        s.status = BackendStatus::Restarting;
+        s.restart_count += 1;
+        s.last_error = Some(error);
+        s.ready_at = None;
+        true
+    });
+}
+
+/// The supervisor gave up on the backend.
+pub(crate) fn failed(app: &AppHandle, backend: &Backend, error: String) {
+    transition(app, backend, |s| {
+        s.status = BackendStatus::Failed;
+        s.last_error = Some(error);
+        s.ready_at = None;
+        true
+    });
+}
+
+/// The backend was stopped on purpose.
+pub(crate) fn stopped(app: &AppHandle, backend: &Backend) {
+    transition(app, backend, |s| {
+        let changed = s.status != BackendStatus::Stopped;
+        s.status = BackendStatus::Stopped;
+        s.ready_at = None;
+        changed
+    });
+}
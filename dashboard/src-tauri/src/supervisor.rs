@@ -0,0 +1,336 @@
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+use std::sync::Arc;
+use tauri::AppHandle;
+use tracing::{error, info, info_span, warn, Instrument};
+
+use crate::conflict::{resolve_port, ConflictPolicy, PortResolution};
+use crate::health::{check_backend, local_url, wait_for_backend_ready, wait_until_unhealthy};
+use crate::profiles::Backend;
+use crate::sidecar::{monitor_backend, spawn_backend, SHUTDOWN_GRACE};
+use crate::state::{self, BackendStatus};
+use crate::telemetry;
+
+/// Restart behaviour for the backend sidecar.
+#[derive(Debug, Clone)]
+pub(crate) struct RestartPolicy {
+    /// Delay before the first restart
+    pub initial_backoff: Duration,
+    /// Upper bound for the delay between restarts
+    pub max_backoff: Duration,
+    /// A run lasting at least this long resets the backoff
+    pub stable_after: Duration,
+    /// Window in which crashes are counted towards a crash loop
+    pub crash_window: Duration,
+    /// Crashes tolerated within `crash_window` before giving up
+    pub max_crashes: usize,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            stable_after: Duration::from_secs(60),
+            crash_window: Duration::from_secs(300),
+            max_crashes: 5,
+        }
+    }
+}
+
+impl RestartPolicy {
+    /// Capped exponential backoff for the given (1-based) restart attempt.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 1u32.checked_shl(attempt.saturating_sub(1)).unwrap_or(u32::MAX);
+        self.initial_backoff
+            .checked_mul(factor)
+            .map_or(self.max_backoff, |d| d.min(self.max_backoff))
+    }
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RestartingPayload {
+    profile: String,
+    attempt: u32,
+    delay_ms: u64,
+    exit_code: Option<i32>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GaveUpPayload {
+    profile: String,
+    attempt: u32,
+    crashes: usize,
+    reason: String,
+}
+
+/// Health-check a backend the user runs themselves, without spawning anything.
+///
+/// Dev backends restart often (`--reload`), so readiness is tracked until
+/// the profile is deactivated.
+pub(crate) fn attach(app: AppHandle, backend: Arc<Backend>) {
+    let base_url = backend.url();
+    let span = info_span!("attach", profile = %backend.profile, url = %base_url);
+    let task = async move {
+        info!("attaching to external backend");
+        let generation = state::starting(&app, &backend, base_url.clone());
+        let probe = &backend.probe;
+
+        let watch = async {
+            loop {
+                match wait_for_backend_ready(&base_url, probe).await {
+                    Ok(()) => {
+                        state::ready(&app, &backend, generation);
+                        info!("external backend is ready");
+
+                        let error = wait_until_unhealthy(&base_url, probe).await;
+                        warn!(%error, "external backend is unhealthy");
+                        state::degraded(&app, &backend, generation, error);
+                    }
+                    Err(e) => state::degraded(
+                        &app,
+                        &backend,
+                        generation,
+                        format!("External backend is not reachable: {}", e),
+                    ),
+                }
+            }
+        };
+        tokio::select! {
+            _ = watch => {}
+            _ = backend.sidecar.shut_down() => info!("stopped watching external backend"),
+        }
+    };
+    tauri::async_runtime::spawn(task.instrument(span));
+}
+
+/// Start supervising the profile's sidecar in the background.
+pub(crate) fn start(app: AppHandle, backend: Arc<Backend>, policy: RestartPolicy) {
+    tauri::async_runtime::spawn(supervise(app, backend, policy));
+}
+
+/// Relaunch the sidecar, e.g. after the workspace changed. Starts supervising
+/// again if the supervisor had given up.
+pub(crate) async fn restart(app: &AppHandle, backend: Arc<Backend>) {
+    if backend.state.snapshot().status == BackendStatus::Failed {
+        start(app.clone(), backend, RestartPolicy::default());
+    } else {
+        backend.sidecar.restart(SHUTDOWN_GRACE).await;
+    }
+}
+
+async fn supervise(app: AppHandle, backend: Arc<Backend>, policy: RestartPolicy) {
+    let mut attempt: u32 = 0;
+    let mut crashes: VecDeque<Instant> = VecDeque::new();
+    let sidecar = &backend.sidecar;
+
+    loop {
+        let started = Instant::now();
+        // This launch satisfies any restart requested while none was running
+        sidecar.take_restart_request();
+
+        let wanted = sidecar.wanted_port();
+        let resolution = resolve_port(&app, &backend, wanted, ConflictPolicy::from_env()).await;
+
+        let (exit_code, reason) = match resolution {
+            Ok(PortResolution::Spawn(port)) => {
+                let generation = state::starting(&app, &backend, local_url(port));
+                let span = info_span!("launch", profile = %backend.profile, generation, port, attempt);
+                async {
+                    match spawn_backend(&app, &backend, port) {
+                        Ok(rx) => {
+                            watch_health(app.clone(), backend.clone(), local_url(port), generation, true);
+                            let code = monitor_backend(&app, &backend, rx, generation).await;
+                            (code, format!("Sidecar exited with code {:?}", code))
+                        }
+                        Err(e) => {
+                            warn!(error = %e, "backend sidecar failed to start");
+                            (None, e)
+                        }
+                    }
+                }
+                .instrument(span)
+                .await
+            }
+            Ok(PortResolution::Adopt { port, pid }) => {
+                sidecar.set_port(port);
+                sidecar.adopt(&app, pid);
+                let generation = state::starting(&app, &backend, local_url(port));
+                let span = info_span!("adopt", profile = %backend.profile, generation, port, ?pid);
+                async {
+                    info!("adopted running backend");
+                    watch_health(app.clone(), backend.clone(), local_url(port), generation, false);
+                    let error = wait_until_unhealthy(&local_url(port), &backend.probe).await;
+                    warn!(%error, "adopted backend is unhealthy");
+                }
+                .instrument(span)
+                .await;
+                sidecar.release_adopted();
+                (None, "Adopted backend stopped responding".to_string())
+            }
+            Err(e) => {
+                warn!(error = %e, "could not resolve backend port");
+                (None, e)
+            }
+        };
+
+        if sidecar.is_shutting_down() {
+            info!(profile = %backend.profile, "backend stopped for shutdown, not restarting");
+            return;
+        }
+        if sidecar.take_restart_request() {
+            info!("restarting backend on request");
+            attempt = 0;
+            crashes.clear();
+            continue;
+        }
+
+        // A long healthy run means the next crash is not part of a loop
+        if started.elapsed() >= policy.stable_after {
+            attempt = 0;
+        }
+        attempt += 1;
+
+        let now = Instant::now();
+        crashes.push_back(now);
+        while crashes
+            .front()
+            .is_some_and(|t| now.duration_since(*t) > policy.crash_window)
+        {
+            crashes.pop_front();
+        }
+
+        if crashes.len() > policy.max_crashes {
+            error!(
+                profile = %backend.profile,
+                crashes = crashes.len(),
+                window_secs = policy.crash_window.as_secs(),
+                %reason,
+                "backend is crash looping, giving up"
+            );
+            telemetry::emit(
+                &app,
+                "backend-gave-up",
+                GaveUpPayload {
+                    profile: backend.profile.clone(),
+                    attempt,
+                    crashes: crashes.len(),
+                    reason: reason.clone(),
+                },
+            );
+            state::failed(&app, &backend, reason);
+            return;
+        }
+
+        let delay = policy.backoff(attempt);
+        info!(
+            profile = %backend.profile,
+            attempt,
+            delay_ms = delay.as_millis() as u64,
+            ?exit_code,
+            %reason,
+            "restarting backend"
+        );
+        telemetry::emit(
+            &app,
+            "backend-restarting",
+            RestartingPayload {
+                profile: backend.profile.clone(),
+                attempt,
+                delay_ms: delay.as_millis() as u64,
+                exit_code,
+            },
+        );
+        state::restarting(&app, &backend, reason);
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = sidecar.requested_restart() => {
+                info!("restarting backend on request, skipping the backoff");
+                attempt = 0;
+                crashes.clear();
+            }
+            _ = sidecar.shut_down() => {}
+        }
+
+        if sidecar.is_shutting_down() {
+            return;
+        }
+    }
+}
+
+/// Wait for the freshly launched backend in background and update its state,
+/// then keep probing it while `liveness` is set and the launch is current.
+fn watch_health(app: AppHandle, backend: Arc<Backend>, base_url: String, generation: u64, liveness: bool) {
+    let span = info_span!("health", profile = %backend.profile, generation, url = %base_url);
+    let task = async move {
+        let probe = &backend.probe;
+        match wait_for_backend_ready(&base_url, probe).await {
+            Ok(()) => {
+                state::ready(&app, &backend, generation);
+                info!("backend is ready, frontend can connect");
+            }
+            Err(e) => {
+                error!(error = %e, "backend failed to become ready");
+                state::degraded(&app, &backend, generation, format!("Backend did not become healthy: {}", e));
+            }
+        }
+
+        if !liveness {
+            return;
+        }
+
+        let client = probe.client();
+        let mut failures = 0;
+        loop {
+            tokio::time::sleep(probe.liveness_interval()).await;
+            if !state::is_current(&backend, generation) {
+                return;
+            }
+            match check_backend(&client, &base_url, probe).await {
+                Ok(()) => {
+                    failures = 0;
+                    state::ready(&app, &backend, generation);
+                }
+                Err(e) => {
+                    failures += 1;
+                    warn!(failures, error = %e, "liveness check failed");
+                    if failures >= probe.liveness_failure_threshold.max(1) {
+                        state::degraded(&app, &backend, generation, e);
+                    }
+                }
+            }
+        }
+    };
+    tauri::async_runtime::spawn(task.instrument(span));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let policy = RestartPolicy::default();
+        let delays: Vec<u64> = (1..=8).map(|attempt| policy.backoff(attempt).as_millis() as u64).collect();
+        assert_eq!(delays, [500, 1_000, 2_000, 4_000, 8_000, 16_000, 30_000, 30_000]);
+    }
+
+    #[test]
+    fn backoff_never_overflows() {
+        let policy = RestartPolicy::default();
+        assert_eq!(policy.backoff(0), policy.initial_backoff);
+        for attempt in [31, 32, 33, 64, u32::MAX] {
+            assert_eq!(policy.backoff(attempt), policy.max_backoff);
+        }
+
+        let policy = RestartPolicy {
+            initial_backoff: Duration::MAX,
+            max_backoff: Duration::MAX,
+            ..RestartPolicy::default()
+        };
+        assert_eq!(policy.backoff(2), Duration::MAX);
+    }
+}
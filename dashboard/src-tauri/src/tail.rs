@@ -0,0 +1,622 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::SeekFrom;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::ipc::Channel;
+use tauri::{AppHandle, Manager};
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use tokio::sync::{watch, Notify};
+use tracing::{debug, info, info_span, warn, Instrument};
+
+/// Delay between checks for new data once the end of the log is reached
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Bytes read per read call
+const READ_CHUNK: usize = 256 * 1024;
+
+/// A batch is sent once it holds this many entries or bytes
+const MAX_BATCH_ENTRIES: usize = 500;
+const MAX_BATCH_BYTES: u64 = 1024 * 1024;
+
+/// Batches sent but not yet acknowledged before the tailer stops reading
+const MAX_IN_FLIGHT: u64 = 4;
+
+/// Bytes of history sent when a tail starts, unless an offset is given
+const DEFAULT_BACKLOG_BYTES: u64 = 1024 * 1024;
+
+/// Longest line kept; longer ones are skipped rather than buffered
+const MAX_LINE_BYTES: usize = 16 * 1024 * 1024;
+
+/// Longest tool result text sent to the log viewer; the rest is cut off
+const MAX_RESULT_CHARS: usize = 10_000;
+
+/// Where a tail starts.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct TailOptions {
+    /// Byte offset to resume from, as reported in an earlier batch
+    pub offset: Option<u64>,
+    /// Bytes of history to send before following; defaults to 1 MiB
+    pub backlog_bytes: Option<u64>,
+}
+
+/// An entry of a Claude stream log, as sent to the log viewer.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub(crate) enum StreamEntry {
+    #[serde(rename_all = "camelCase")]
+    AssistantText { offset: u64, text: String },
+    #[serde(rename_all = "camelCase")]
+    ToolUse {
+        offset: u64,
+        id: Option<String>,
+        name: String,
+        input: Value,
+    },
+    #[serde(rename_all = "camelCase")]
+    ToolResult {
+        offset: u64,
+        tool_use_id: Option<String>,
+        content: String,
+        truncated: bool,
+        is_error: bool,
+    },
+    #[serde(rename_all = "camelCase")]
+    Error { offset: u64, message: String },
+    /// Any other line, e.g. `system` or a successful `result`
+    #[serde(rename_all = "camelCase")]
+    Other {
+        offset: u64,
+        entry_type: Option<String>,
+        subtype: Option<String>,
+    },
+}
+
+/// Entries sent over a tail's channel; acknowledge `seq` with `ack_agent_log`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TailBatch {
+    pub seq: u64,
+    pub entries: Vec<StreamEntry>,
+    /// Offset to resume from to continue after this batch
+    pub offset: u64,
+    /// The log was rotated or truncated and is read again from the start
+    pub rotated: bool,
+    /// Lines that weren't valid JSON or were too long
+    pub skipped: u64,
+}
+
+struct Tail {
+    label: String,
+    acked: watch::Sender<u64>,
+    stop: Arc<Notify>,
+}
+
+/// Stream-log tails of all windows.
+#[derive(Default)]
+pub(crate) struct LogTails {
+    next: AtomicU32,
+    tails: Mutex<HashMap<u32, Tail>>,
+}
+
+impl LogTails {
+    /// Follow the log at `path` for the window labelled `label`. Returns the tail's ID.
+    pub(crate) fn start(
+        &self,
+        app: &AppHandle,
+        label: &str,
+        path: PathBuf,
+        options: TailOptions,
+        channel: Channel<TailBatch>,
+    ) -> u32 {
+        let id = self.next.fetch_add(1, Ordering::SeqCst) + 1;
+        let (acked, acks) = watch::channel(0);
+        let stop = Arc::new(Notify::new());
+        self.tails.lock().unwrap().insert(
+            id,
+            Tail {
+                label: label.to_string(),
+                acked,
+                stop: stop.clone(),
+            },
+        );
+
+        let app = app.clone();
+        let span = info_span!("tail", id, path = %path.display());
+        let task = async move {
+            info!("tailing stream log");
+            let mut reader = Reader::new(path, options);
+            if let Err(e) = follow(&mut reader, &channel, acks, &stop).await {
+                debug!(error = %e, "tail ended");
+            }
+            app.state::<LogTails>().tails.lock().unwrap().remove(&id);
+        };
+        tauri::async_runtime::spawn(task.instrument(span));
+        id
+    }
+
+    /// Mark batches up to `seq` of tail `id` as rendered.
+    pub(crate) fn ack(&self, id: u32, seq: u64) {
+        if let Some(tail) = self.tails.lock().unwrap().get(&id) {
+            tail.acked.send_if_modified(|acked| {
+                let newer = seq > *acked;
+                if newer {
+                    *acked = seq;
+                }
+                newer
+            });
+        }
+    }
+
+    pub(crate) fn stop(&self, id: u32) {
+        if let Some(tail) = self.tails.lock().unwrap().remove(&id) {
+            tail.stop.notify_one();
+        }
+    }
+
+    /// Stop the tails of a closed window.
+    pub(crate) fn forget_window(&self, label: &str) {
+        self.tails.lock().unwrap().retain(|_, tail| {
+            let keep = tail.label != label;
+            if !keep {
+                tail.stop.notify_one();
+            }
+            keep
+        });
+    }
+}
+
+/// Send batches until the window stops the tail or the channel closes.
+/// Reading pauses while `MAX_IN_FLIGHT` batches are unacknowledged.
+async fn follow(
+    reader: &mut Reader,
+    channel: &Channel<TailBatch>,
+    mut acks: watch::Receiver<u64>,
+    stop: &Notify,
+) -> Result<(), String> {
+    let mut seq: u64 = 0;
+    loop {
+        tokio::select! {
+            acked = acks.wait_for(|acked| seq.saturating_sub(*acked) < MAX_IN_FLIGHT) => {
+                acked.map_err(|e| e.to_string())?;
+            }
+            _ = stop.notified() => return Ok(()),
+        }
+
+        match reader.next_batch().await? {
+            Some(mut batch) => {
+                seq += 1;
+                batch.seq = seq;
+                channel.send(batch).map_err(|e| e.to_string())?;
+            }
+            None => tokio::select! {
+                _ = tokio::time::sleep(POLL_INTERVAL) => {}
+                _ = stop.notified() => return Ok(()),
+            },
+        }
+    }
+}
+
+/// Incremental reader of a JSONL file that survives rotation and truncation.
+struct Reader {
+    path: PathBuf,
+    options: TailOptions,
+    file: Option<tokio::fs::File>,
+    /// Identity of the open file, to notice it being replaced
+    file_id: Option<u64>,
+    /// Offset of the first byte of `pending`
+    offset: u64,
+    /// Bytes read after the last complete line
+    pending: Vec<u8>,
+    /// Drop everything up to the first newline, when starting mid-line
+    skip_partial: bool,
+    /// Lines skipped over since the last batch
+    skipped: u64,
+    opened_before: bool,
+}
+
+impl Reader {
+    fn new(path: PathBuf, options: TailOptions) -> Self {
+        Self {
+            path,
+            options,
+            file: None,
+            file_id: None,
+            offset: 0,
+            pending: Vec::new(),
+            skip_partial: false,
+            skipped: 0,
+            opened_before: false,
+        }
+    }
+
+    /// Entries appended since the last call; `None` if there are none.
+    async fn next_batch(&mut self) -> Result<Option<TailBatch>, String> {
+        if self.file.is_some() && self.replaced().await {
+            info!("stream log rotated, reading from the start");
+            self.file = None;
+            self.pending.clear();
+        }
+        // Reopening after a rotation whose replacement may have appeared later
+        let rotated = self.file.is_none() && self.opened_before;
+        if self.file.is_none() && !self.open().await? {
+            return Ok(None);
+        }
+
+        let mut entries = Vec::new();
+        let mut read_bytes = 0;
+        let mut chunk = vec![0; READ_CHUNK];
+        while entries.len() < MAX_BATCH_ENTRIES && read_bytes < MAX_BATCH_BYTES {
+            let n = match self.file.as_mut() {
+                Some(file) => file.read(&mut chunk).await.map_err(|e| e.to_string())?,
+                None => break,
+            };
+            if n == 0 {
+                break;
+            }
+            read_bytes += n as u64;
+            self.pending.extend_from_slice(&chunk[..n]);
+            self.take_lines(&mut entries);
+        }
+
+        if entries.is_empty() && !rotated && self.skipped == 0 {
+            return Ok(None);
+        }
+        Ok(Some(TailBatch {
+            seq: 0,
+            entries,
+            offset: self.offset,
+            rotated,
+            skipped: std::mem::take(&mut self.skipped),
+        }))
+    }
+
+    /// Open the log at the start offset; `false` if it doesn't exist yet.
+    async fn open(&mut self) -> Result<bool, String> {
+        let mut file = match tokio::fs::File::open(&self.path).await {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+            Err(e) => return Err(format!("Cannot open {}: {}", self.path.display(), e)),
+        };
+        let metadata = file.metadata().await.map_err(|e| e.to_string())?;
+        let len = metadata.len();
+
+        // A replacement file is read whole; the first one from the requested offset
+        let (start, skip_partial) = if self.opened_before {
+            (0, false)
+        } else if let Some(offset) = self.options.offset.filter(|&offset| offset <= len) {
+            (offset, false)
+        } else {
+            let backlog = self.options.backlog_bytes.unwrap_or(DEFAULT_BACKLOG_BYTES);
+            let start = len.saturating_sub(backlog);
+            (start, start > 0)
+        };
+        file.seek(SeekFrom::Start(start)).await.map_err(|e| e.to_string())?;
+
+        self.file = Some(file);
+        self.file_id = file_id(&metadata);
+        self.offset = start;
+        self.skip_partial = skip_partial;
+        self.opened_before = true;
+        Ok(true)
+    }
+
+    /// Whether the path now names another file, or the file shrank below what was read.
+    async fn replaced(&self) -> bool {
+        let Ok(metadata) = tokio::fs::metadata(&self.path).await else {
+            return false;
+        };
+        let read_to = self.offset + self.pending.len() as u64;
+        file_id(&metadata) != self.file_id || metadata.len() < read_to
+    }
+
+    /// Parse the complete lines in `pending` into `entries`.
+    fn take_lines(&mut self, entries: &mut Vec<StreamEntry>) {
+        let mut start = 0;
+        while let Some(end) = self.pending[start..].iter().position(|&b| b == b'\n') {
+            let line = &self.pending[start..start + end];
+            let offset = self.offset + start as u64;
+            if std::mem::take(&mut self.skip_partial) {
+                // The start of this line was before the backlog
+            } else if line.iter().all(u8::is_ascii_whitespace) {
+                // Blank lines are not entries
+            } else if !parse_line(offset, line, Some(MAX_RESULT_CHARS), entries) {
+                self.skipped += 1;
+            }
+            start += end + 1;
+        }
+        self.pending.drain(..start);
+        self.offset += start as u64;
+
+        if self.pending.len() > MAX_LINE_BYTES {
+            warn!(offset = self.offset, "skipping overlong line");
+            self.offset += self.pending.len() as u64;
+            self.pending.clear();
+            self.skip_partial = true;
+            self.skipped += 1;
+        }
+    }
+}
+
+#[cfg(unix)]
+pub(crate) fn file_id(metadata: &std::fs::Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.ino())
+}
+
+#[cfg(not(unix))]
+pub(crate) fn file_id(_metadata: &std::fs::Metadata) -> Option<u64> {
+    None
+}
+
+/// Turn one line of a stream log into entries, cutting tool results to
+/// `max_result_chars` if given; `false` if it isn't JSON.
+pub(crate) fn parse_line(
+    offset: u64,
+    line: &[u8],
+    max_result_chars: Option<usize>,
+    entries: &mut Vec<StreamEntry>,
+) -> bool {
+    let Ok(value) = serde_json::from_slice::<Value>(line) else {
+        return false;
+    };
+    let text = |value: Option<&Value>| value.and_then(Value::as_str).map(str::to_string);
+    let entry_type = text(value.get("type"));
+    let before = entries.len();
+
+    match entry_type.as_deref() {
+        Some("assistant") | Some("user") => {
+            match value.pointer("/message/content") {
+                Some(Value::String(content)) if entry_type.as_deref() == Some("assistant") => {
+                    entries.push(StreamEntry::AssistantText {
+                        offset,
+                        text: content.clone(),
+                    });
+                }
+                Some(Value::Array(items)) => {
+                    for item in items {
+                        match item.get("type").and_then(Value::as_str) {
+                            Some("text") if entry_type.as_deref() == Some("assistant") => {
+                                entries.push(StreamEntry::AssistantText {
+                                    offset,
+                                    text: text(item.get("text")).unwrap_or_default(),
+                                });
+                            }
+                            Some("tool_use") => entries.push(StreamEntry::ToolUse {
+                                offset,
+                                id: text(item.get("id")),
+                                name: text(item.get("name")).unwrap_or_default(),
+                                input: item.get("input").cloned().unwrap_or(Value::Null),
+                            }),
+                            Some("tool_result") => {
+                                let (content, truncated) = result_text(item.get("content"), max_result_chars);
+                                entries.push(StreamEntry::ToolResult {
+                                    offset,
+                                    tool_use_id: text(item.get("tool_use_id")),
+                                    content,
+                                    truncated,
+                                    is_error: item.get("is_error").and_then(Value::as_bool).unwrap_or(false),
+                                });
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        Some("error") => entries.push(StreamEntry::Error {
+            offset,
+            message: error_message(&value),
+        }),
+        Some("result") if value.get("is_error").and_then(Value::as_bool) == Some(true) => {
+            entries.push(StreamEntry::Error {
+                offset,
+                message: text(value.get("result"))
+                    .or_else(|| text(value.get("subtype")))
+                    .unwrap_or_else(|| "Agent run failed".to_string()),
+            });
+        }
+        _ => {}
+    }
+
+    if entries.len() == before {
+        entries.push(StreamEntry::Other {
+            offset,
+            entry_type,
+            subtype: text(value.get("subtype")),
+        });
+    }
+    true
+}
+
+fn error_message(value: &Value) -> String {
+    match value.get("error").or_else(|| value.get("message")) {
+        Some(Value::String(message)) => message.clone(),
+        Some(Value::Object(error)) => match error.get("message") {
+            Some(Value::String(message)) => message.clone(),
+            _ => Value::Object(error.clone()).to_string(),
+        },
+        Some(other) => other.to_string(),
+        None => value.to_string(),
+    }
+}
+
+/// Text of a tool result, which is a string or a list of content blocks,
+/// cut to `max_chars` if given.
+fn result_text(content: Option<&Value>, max_chars: Option<usize>) -> (String, bool) {
+    let full = match content {
+        Some(Value::String(text)) => text.clone(),
+        Some(Value::Array(blocks)) => blocks
+            .iter()
+            .filter_map(|block| block.get("text").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Some(Value::Null) | None => String::new(),
+        Some(other) => other.to_string(),
+    };
+    match max_chars.and_then(|max| full.char_indices().nth(max)) {
+        Some((cut, _)) => (full[..cut].to_string(), true),
+        None => (full, false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn parse(line: &str, max_result_chars: Option<usize>) -> Vec<StreamEntry> {
+        let mut entries = Vec::new();
+        assert!(parse_line(7, line.as_bytes(), max_result_chars, &mut entries));
+        entries
+    }
+
+    fn append(path: &std::path::Path, data: &str) {
+        let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path).unwrap();
+        file.write_all(data.as_bytes()).unwrap();
+    }
+
+    fn offsets(batch: &TailBatch) -> Vec<u64> {
+        batch
+            .entries
+            .iter()
+            .map(|entry| match entry {
+                StreamEntry::Other { offset, .. } => *offset,
+                other => panic!("unexpected entry {:?}", other),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn assistant_content_blocks() {
+        let entries = parse(
+            r#"{"type":"assistant","message":{"content":[
+                {"type":"text","text":"Looking"},
+                {"type":"tool_use","id":"t1","name":"Read","input":{"file":"a.py"}}]}}"#,
+            None,
+        );
+        assert!(matches!(&entries[..], [
+            StreamEntry::AssistantText { offset: 7, text },
+            StreamEntry::ToolUse { id: Some(id), name, .. },
+        ] if text == "Looking" && id == "t1" && name == "Read"));
+    }
+
+    #[test]
+    fn tool_result_is_cut_to_max_chars() {
+        let line = r#"{"type":"user","message":{"content":[
+            {"type":"tool_result","tool_use_id":"t1","content":[{"text":"héllo"},{"text":"world"}]}]}}"#;
+        assert!(matches!(&parse(line, Some(3))[..], [
+            StreamEntry::ToolResult { content, truncated: true, is_error: false, .. }
+        ] if content == "hél"));
+        assert!(matches!(&parse(line, None)[..], [
+            StreamEntry::ToolResult { content, truncated: false, .. }
+        ] if content == "héllo\nworld"));
+    }
+
+    #[test]
+    fn user_text_is_not_assistant_text() {
+        let entries = parse(r#"{"type":"user","message":{"content":"hi"}}"#, None);
+        assert!(matches!(&entries[..], [StreamEntry::Other { entry_type: Some(t), .. }] if t == "user"));
+    }
+
+    #[test]
+    fn errors() {
+        assert!(matches!(&parse(r#"{"type":"error","error":{"message":"overloaded"}}"#, None)[..], [
+            StreamEntry::Error { message, .. }
+        ] if message == "overloaded"));
+        assert!(matches!(&parse(r#"{"type":"result","is_error":true,"subtype":"error_max_turns"}"#, None)[..], [
+            StreamEntry::Error { message, .. }
+        ] if message == "error_max_turns"));
+        assert!(matches!(&parse(r#"{"type":"result","subtype":"success"}"#, None)[..], [
+            StreamEntry::Other { subtype: Some(s), .. }
+        ] if s == "success"));
+    }
+
+    #[test]
+    fn invalid_json_is_rejected() {
+        let mut entries = Vec::new();
+        assert!(!parse_line(0, b"{\"type\":", None, &mut entries));
+        assert!(entries.is_empty());
+    }
+
+    #[tokio::test]
+    async fn partial_lines_wait_for_their_newline() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("agent_stream.jsonl");
+        append(&path, "{\"type\":\"system\"}\n{\"type\":");
+        let mut reader = Reader::new(path.clone(), TailOptions::default());
+
+        let batch = reader.next_batch().await.unwrap().unwrap();
+        assert_eq!(offsets(&batch), [0]);
+        assert_eq!(batch.offset, 18);
+
+        append(&path, "\"system\"}\n\nnot json\n");
+        let batch = reader.next_batch().await.unwrap().unwrap();
+        assert_eq!(offsets(&batch), [18]);
+        assert_eq!(batch.skipped, 1);
+        assert!(!batch.rotated);
+        assert!(reader.next_batch().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn backlog_skips_the_partial_first_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("agent_stream.jsonl");
+        append(&path, "{\"type\":\"system\"}\n{\"type\":\"system\"}\n");
+        let options = TailOptions {
+            offset: None,
+            backlog_bytes: Some(20),
+        };
+        let mut reader = Reader::new(path, options);
+        assert_eq!(offsets(&reader.next_batch().await.unwrap().unwrap()), [18]);
+    }
+
+    #[tokio::test]
+    async fn resumes_from_an_offset() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("agent_stream.jsonl");
+        append(&path, "{\"type\":\"system\"}\n{\"type\":\"system\"}\n");
+        let options = TailOptions {
+            offset: Some(18),
+            backlog_bytes: None,
+        };
+        let mut reader = Reader::new(path, options);
+        assert_eq!(offsets(&reader.next_batch().await.unwrap().unwrap()), [18]);
+    }
+
+    #[tokio::test]
+    async fn truncation_reads_from_the_start() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("agent_stream.jsonl");
+        append(&path, "{\"type\":\"system\"}\n{\"type\":\"system\"}\n");
+        let mut reader = Reader::new(path.clone(), TailOptions::default());
+        assert_eq!(offsets(&reader.next_batch().await.unwrap().unwrap()), [0, 18]);
+
+        std::fs::write(&path, "{\"type\":\"result\"}\n").unwrap();
+        let batch = reader.next_batch().await.unwrap().unwrap();
+        assert!(batch.rotated);
+        assert_eq!(offsets(&batch), [0]);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn replaced_file_is_read_whole() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("agent_stream.jsonl");
+        append(&path, "{\"type\":\"system\"}\n");
+        let mut reader = Reader::new(path.clone(), TailOptions::default());
+        assert_eq!(offsets(&reader.next_batch().await.unwrap().unwrap()), [0]);
+
+        let rotated = dir.path().join("new.jsonl");
+        append(&rotated, "{\"type\":\"system\"}\n{\"type\":\"system\"}\n");
+        std::fs::rename(&rotated, &path).unwrap();
+        let batch = reader.next_batch().await.unwrap().unwrap();
+        assert!(batch.rotated);
+        assert_eq!(offsets(&batch), [0, 18]);
+    }
+}
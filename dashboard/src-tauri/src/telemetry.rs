@@ -0,0 +1,129 @@
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+use tauri::{AppHandle, Emitter};
+use tracing::{debug, warn};
+use tracing_subscriber::filter::filter_fn;
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::{fmt, reload, EnvFilter, Registry};
+
+use crate::logfile::CoreLogWriter;
+
+/// Environment variable overriding the log filter, e.g. `debug` or `info,claude_orchestrator_dashboard_lib::health=trace`
+pub(crate) const LOG_ENV: &str = "CLAUDE_ORCHESTRATOR_LOG";
+
+/// Environment variable overriding the log format (`text` or `json`)
+pub(crate) const LOG_FORMAT_ENV: &str = "CLAUDE_ORCHESTRATOR_LOG_FORMAT";
+
+/// Target used for relayed sidecar output, which has its own log file
+pub(crate) const SIDECAR_TARGET: &str = "sidecar";
+
+static FILTER: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum LogFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+/// Logging options, from the `logging` section of the settings file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct LoggingConfig {
+    /// Filter directive, e.g. `info` or `warn,claude_orchestrator_dashboard_lib::supervisor=debug`
+    pub level: String,
+    pub format: LogFormat,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            level: "info".to_string(),
+            format: LogFormat::Text,
+        }
+    }
+}
+
+fn parse_filter(directive: &str) -> Result<EnvFilter, String> {
+    EnvFilter::try_new(directive).map_err(|e| format!("Invalid log level {:?}: {}", directive, e))
+}
+
+/// Install the global subscriber: console plus `core.log`, with a reloadable filter.
+pub(crate) fn init(config: &LoggingConfig) {
+    let directive = std::env::var(LOG_ENV).unwrap_or_else(|_| config.level.clone());
+    let (filter, filter_error) = match parse_filter(&directive) {
+        Ok(filter) => (filter, None),
+        Err(e) => (EnvFilter::new("info"), Some(e)),
+    };
+    let format = match std::env::var(LOG_FORMAT_ENV).as_deref() {
+        Ok("json") => LogFormat::Json,
+        Ok("text") => LogFormat::Text,
+        _ => config.format,
+    };
+
+    let (filter, handle) = reload::Layer::new(filter);
+    // Sidecar output already goes to backend.log
+    let core_only = || filter_fn(|meta| meta.target() != SIDECAR_TARGET);
+    let registry = tracing_subscriber::registry().with(filter);
+
+    let result = match format {
+        LogFormat::Text => registry
+            .with(fmt::layer())
+            .with(
+                fmt::layer()
+                    .with_ansi(false)
+                    .with_writer(CoreLogWriter)
+                    .with_filter(core_only()),
+            )
+            .try_init(),
+        LogFormat::Json => registry
+            .with(fmt::layer().json().with_span_list(true))
+            .with(
+                fmt::layer()
+                    .json()
+                    .with_span_list(true)
+                    .with_writer(CoreLogWriter)
+                    .with_filter(core_only()),
+            )
+            .try_init(),
+    };
+
+    if result.is_ok() {
+        let _ = FILTER.set(handle);
+    }
+    if let Some(e) = filter_error {
+        warn!(error = %e, "falling back to info logging");
+    }
+}
+
+/// Current filter directive.
+pub(crate) fn level() -> Option<String> {
+    FILTER.get()?.with_current(|filter| filter.to_string()).ok()
+}
+
+/// Replace the filter directive at runtime.
+pub(crate) fn set_level(directive: &str) -> Result<(), String> {
+    let filter = parse_filter(directive)?;
+    FILTER
+        .get()
+        .ok_or("Logging is not initialized")?
+        .reload(filter)
+        .map_err(|e| e.to_string())
+}
+
+/// Emit a Tauri event to all windows, tracing the emission.
+pub(crate) fn emit<S: Serialize + Clone>(app: &AppHandle, event: &str, payload: S) {
+    match app.emit(event, payload) {
+        Ok(()) => debug!(event, "emitted event"),
+        Err(e) => warn!(event, error = %e, "failed to emit event"),
+    }
+}
+
+/// Emit a Tauri event to the window labelled `label` only, tracing the emission.
+pub(crate) fn emit_to<S: Serialize + Clone>(app: &AppHandle, label: &str, event: &str, payload: S) {
+    match app.emit_to(label, event, payload) {
+        Ok(()) => debug!(event, label, "emitted event"),
+        Err(e) => warn!(event, label, error = %e, "failed to emit event"),
+    }
+}
@@ -0,0 +1,197 @@
+use portable_pty::{native_pty_system, ChildKiller, CommandBuilder, MasterPty, PtySize};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Mutex;
+use tauri::ipc::{Channel, InvokeResponseBody};
+use tauri::{AppHandle, Manager};
+use tracing::{debug, info, warn};
+
+use crate::launcher::validate_session;
+use crate::telemetry;
+
+/// Bytes read from the terminal at once
+const READ_CHUNK: usize = 16 * 1024;
+
+/// How a terminal is attached to its tmux session.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum TerminalMode {
+    /// Watch only: tmux ignores input and `write_terminal` refuses it
+    #[default]
+    ReadOnly,
+    /// Type into the agent's session, as if attached from a shell
+    Takeover,
+}
+
+/// `terminal-exited` payload.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TerminalExitedPayload {
+    id: u32,
+    /// Exit code of `tmux attach`; `None` if it was closed from the app
+    code: Option<u32>,
+}
+
+struct Terminal {
+    label: String,
+    mode: TerminalMode,
+    master: Box<dyn MasterPty + Send>,
+    writer: Box<dyn Write + Send>,
+    killer: Box<dyn ChildKiller + Send + Sync>,
+}
+
+/// tmux sessions attached in a PTY, by terminal ID.
+#[derive(Default)]
+pub(crate) struct Terminals {
+    next: AtomicU32,
+    terminals: Mutex<HashMap<u32, Terminal>>,
+}
+
+impl Terminals {
+    /// Attach to tmux `session` in a PTY of `cols` x `rows` for the window
+    /// labelled `label`, sending its output to `channel`. Returns the terminal's ID.
+    pub(crate) fn open(
+        &self,
+        app: &AppHandle,
+        label: &str,
+        session: &str,
+        mode: TerminalMode,
+        size: (u16, u16),
+        channel: Channel<InvokeResponseBody>,
+    ) -> Result<u32, String> {
+        validate_session(session)?;
+        let (cols, rows) = size;
+        let pair = native_pty_system()
+            .openpty(pty_size(cols, rows))
+            .map_err(|e| format!("Failed to open a terminal: {}", e))?;
+
+        let mut command = CommandBuilder::new("tmux");
+        command.arg("attach-session");
+        if mode == TerminalMode::ReadOnly {
+            command.arg("-r");
+        }
+        // `=` matches the name exactly rather than as a prefix
+        command.args(["-t", &format!("={}", session)]);
+        command.env("TERM", "xterm-256color");
+        let mut child = pair
+            .slave
+            .spawn_command(command)
+            .map_err(|e| format!("Failed to run tmux: {}", e))?;
+        // Only the child needs the slave side; keeping it open would hide its exit
+        drop(pair.slave);
+
+        let mut reader = pair
+            .master
+            .try_clone_reader()
+            .map_err(|e| format!("Failed to read the terminal: {}", e))?;
+        let writer = pair
+            .master
+            .take_writer()
+            .map_err(|e| format!("Failed to write to the terminal: {}", e))?;
+
+        let id = self.next.fetch_add(1, Ordering::SeqCst) + 1;
+        self.terminals.lock().unwrap().insert(
+            id,
+            Terminal {
+                label: label.to_string(),
+                mode,
+                master: pair.master,
+                writer,
+                killer: child.clone_killer(),
+            },
+        );
+        info!(id, session, ?mode, "attached terminal");
+
+        std::thread::spawn(move || {
+            let mut buffer = vec![0; READ_CHUNK];
+            loop {
+                match reader.read(&mut buffer) {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => {
+                        if channel.send(InvokeResponseBody::Raw(buffer[..n].to_vec())).is_err() {
+                            break;
+                        }
+                    }
+                }
+            }
+        });
+
+        let app = app.clone();
+        let label = label.to_string();
+        std::thread::spawn(move || {
+            let code = child.wait().ok().map(|status| status.exit_code());
+            let removed = app.state::<Terminals>().terminals.lock().unwrap().remove(&id);
+            // Closed from the app if it was already removed
+            let code = removed.and(code);
+            debug!(id, ?code, "terminal exited");
+            telemetry::emit_to(&app, &label, "terminal-exited", TerminalExitedPayload { id, code });
+        });
+        Ok(id)
+    }
+
+    /// Type `data` into terminal `id`, if it was opened in takeover mode.
+    pub(crate) fn write(&self, id: u32, data: &str) -> Result<(), String> {
+        let mut terminals = self.terminals.lock().unwrap();
+        let terminal = terminals.get_mut(&id).ok_or_else(|| format!("No terminal {}", id))?;
+        if terminal.mode != TerminalMode::Takeover {
+            return Err("The terminal is read-only, reopen it in takeover mode to type".to_string());
+        }
+        terminal
+            .writer
+            .write_all(data.as_bytes())
+            .and_then(|()| terminal.writer.flush())
+            .map_err(|e| format!("Failed to write to terminal {}: {}", id, e))
+    }
+
+    pub(crate) fn resize(&self, id: u32, cols: u16, rows: u16) -> Result<(), String> {
+        let terminals = self.terminals.lock().unwrap();
+        let terminal = terminals.get(&id).ok_or_else(|| format!("No terminal {}", id))?;
+        terminal
+            .master
+            .resize(pty_size(cols, rows))
+            .map_err(|e| format!("Failed to resize terminal {}: {}", id, e))
+    }
+
+    /// Detach terminal `id`; the tmux session and its agent keep running.
+    pub(crate) fn close(&self, id: u32) {
+        if let Some(terminal) = self.terminals.lock().unwrap().remove(&id) {
+            kill(id, terminal);
+        }
+    }
+
+    /// Detach the terminals of a closed window.
+    pub(crate) fn forget_window(&self, label: &str) {
+        let closed: Vec<(u32, Terminal)> = {
+            let mut terminals = self.terminals.lock().unwrap();
+            let ids: Vec<u32> = terminals
+                .iter()
+                .filter(|(_, terminal)| terminal.label == label)
+                .map(|(id, _)| *id)
+                .collect();
+            ids.into_iter()
+                .filter_map(|id| terminals.remove(&id).map(|terminal| (id, terminal)))
+                .collect()
+        };
+        for (id, terminal) in closed {
+            kill(id, terminal);
+        }
+    }
+}
+
+fn kill(id: u32, mut terminal: Terminal) {
+    if let Err(e) = terminal.killer.kill() {
+        warn!(id, error = %e, "failed to stop tmux client");
+    }
+    info!(id, "detached terminal");
+}
+
+fn pty_size(cols: u16, rows: u16) -> PtySize {
+    PtySize {
+        rows: rows.max(1),
+        cols: cols.max(1),
+        pixel_width: 0,
+        pixel_height: 0,
+    }
+}
@@ -0,0 +1,294 @@
+use std::time::Duration;
+use tauri::image::Image;
+use tauri::menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+use tauri::tray::{TrayIcon, TrayIconBuilder};
+use tauri::{AppHandle, Manager};
+use tracing::{debug, info, warn};
+
+use crate::deeplink::NavigateTarget;
+use crate::instance;
+use crate::models::{PhaseStatus, TaskSummary};
+use crate::profiles::{Backend, Profiles};
+use crate::state::BackendStatus;
+use crate::telemetry;
+
+/// ID of the tray icon
+const TRAY_ID: &str = "main";
+
+/// Delay between refreshes of the task list
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Most tasks listed in the menu
+const MAX_TASKS: usize = 15;
+
+/// Longest task description shown in a menu item
+const MAX_DESCRIPTION_CHARS: usize = 40;
+
+/// Phase statuses that wait on a reviewer
+const REVIEW_STATUSES: &[PhaseStatus] = &[PhaseStatus::InReview, PhaseStatus::AwaitingReview, PhaseStatus::UnderReview];
+
+/// Timeout of a tray-triggered health scan, which checks every active agent
+const HEALTH_SCAN_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Prefix of menu item IDs that open a task
+const TASK_ITEM_PREFIX: &str = "task:";
+const OPEN_ITEM: &str = "open";
+const HEALTH_SCAN_ITEM: &str = "health-scan";
+const QUIT_ITEM: &str = "quit";
+
+fn needs_review(task: &TaskSummary) -> bool {
+    task.current_phase
+        .as_ref()
+        .is_some_and(|phase| REVIEW_STATUSES.contains(&phase.status))
+}
+
+fn needs_attention(task: &TaskSummary) -> bool {
+    task.failed_agents > 0 || needs_review(task)
+}
+
+fn task_label(task: &TaskSummary) -> String {
+    let mut description: String = task.description.chars().take(MAX_DESCRIPTION_CHARS).collect();
+    if description.len() < task.description.len() {
+        description.push('…');
+    }
+    let phase = match &task.current_phase {
+        Some(phase) => format!("{} ({})", phase.name, status_label(phase.status)),
+        None => "no phase".to_string(),
+    };
+    let mut agents = format!("{} running", task.active_agents);
+    if task.failed_agents > 0 {
+        agents.push_str(&format!(", {} failed", task.failed_agents));
+    }
+    format!("{} - {} · {}", description, phase, agents)
+}
+
+/// `IN_REVIEW` as `in review`
+fn status_label(status: PhaseStatus) -> String {
+    serde_json::to_value(status)
+        .ok()
+        .and_then(|value| value.as_str().map(|status| status.to_lowercase().replace('_', " ")))
+        .unwrap_or_default()
+}
+
+/// What the tray last showed, so the menu is only rebuilt when it changes.
+#[derive(Debug, Clone, PartialEq)]
+enum Snapshot {
+    /// The default profile's backend isn't serving requests
+    Offline(BackendStatus),
+    /// `/api/tasks` could not be read
+    Unavailable,
+    Tasks(Vec<TaskSummary>),
+}
+
+impl Snapshot {
+    fn needs_attention(&self) -> bool {
+        match self {
+            Self::Offline(status) => *status == BackendStatus::Failed,
+            Self::Unavailable => false,
+            Self::Tasks(tasks) => tasks.iter().any(needs_attention),
+        }
+    }
+
+    fn tooltip(&self) -> String {
+        match self {
+            Self::Offline(status) => {
+                let status = format!("{:?}", status).to_lowercase();
+                format!("Claude Orchestrator - backend {}", status)
+            }
+            Self::Unavailable => "Claude Orchestrator - tasks unavailable".to_string(),
+            Self::Tasks(tasks) => {
+                let failed: i64 = tasks.iter().map(|task| task.failed_agents).sum();
+                let reviews = tasks.iter().filter(|task| needs_review(task)).count();
+                let mut tooltip = format!("Claude Orchestrator - {} active tasks", tasks.len());
+                if failed > 0 {
+                    tooltip.push_str(&format!(", {} failed agents", failed));
+                }
+                if reviews > 0 {
+                    tooltip.push_str(&format!(", {} awaiting review", reviews));
+                }
+                tooltip
+            }
+        }
+    }
+}
+
+/// Create the tray icon and start keeping it up to date with the default profile's tasks.
+pub(crate) fn init(app: &AppHandle) -> tauri::Result<()> {
+    let icon = app
+        .default_window_icon()
+        .cloned()
+        .ok_or_else(|| tauri::Error::AssetNotFound("default window icon".into()))?;
+    let snapshot = Snapshot::Unavailable;
+
+    TrayIconBuilder::with_id(TRAY_ID)
+        .icon(icon)
+        .tooltip(snapshot.tooltip())
+        .menu(&build_menu(app, &snapshot)?)
+        .show_menu_on_left_click(true)
+        .on_menu_event(on_menu_event)
+        .build(app)?;
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut shown = None;
+        loop {
+            let snapshot = poll(&app).await;
+            if shown.as_ref() != Some(&snapshot) {
+                if let Err(e) = update(&app, &snapshot) {
+                    warn!(error = %e, "failed to update tray");
+                }
+                shown = Some(snapshot);
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    });
+    Ok(())
+}
+
+async fn poll(app: &AppHandle) -> Snapshot {
+    let profiles = app.state::<Profiles>();
+    let Some(backend) = profiles.backend(&profiles.default_profile()) else {
+        return Snapshot::Offline(BackendStatus::Stopped);
+    };
+    let status = backend.state.snapshot().status;
+    if !matches!(status, BackendStatus::Ready | BackendStatus::Degraded) {
+        return Snapshot::Offline(status);
+    }
+
+    match fetch_tasks(&backend).await {
+        Ok(mut tasks) => {
+            // Tasks that need someone come first
+            tasks.sort_by_key(|task| !needs_attention(task));
+            Snapshot::Tasks(tasks)
+        }
+        Err(e) => {
+            debug!(error = %e, "failed to fetch tasks for tray");
+            Snapshot::Unavailable
+        }
+    }
+}
+
+async fn fetch_tasks(backend: &Backend) -> Result<Vec<TaskSummary>, reqwest::Error> {
+    backend
+        .probe
+        .client()
+        .get(format!("{}/api/tasks", backend.url()))
+        .query(&[("status", "ACTIVE"), ("since", "all")])
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await
+}
+
+fn update(app: &AppHandle, snapshot: &Snapshot) -> tauri::Result<()> {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return Ok(());
+    };
+    tray.set_menu(Some(build_menu(app, snapshot)?))?;
+    tray.set_tooltip(Some(snapshot.tooltip()))?;
+    set_attention(app, &tray, snapshot.needs_attention())
+}
+
+fn build_menu(app: &AppHandle, snapshot: &Snapshot) -> tauri::Result<Menu<tauri::Wry>> {
+    let menu = Menu::new(app)?;
+    match snapshot {
+        Snapshot::Offline(_) | Snapshot::Unavailable => {
+            menu.append(&MenuItem::new(app, snapshot.tooltip().as_str(), false, None::<&str>)?)?;
+        }
+        Snapshot::Tasks(tasks) if tasks.is_empty() => {
+            menu.append(&MenuItem::new(app, "No active tasks", false, None::<&str>)?)?;
+        }
+        Snapshot::Tasks(tasks) => {
+            for task in tasks.iter().take(MAX_TASKS) {
+                let id = format!("{}{}", TASK_ITEM_PREFIX, task.task_id);
+                menu.append(&MenuItem::with_id(app, id, task_label(task).as_str(), true, None::<&str>)?)?;
+            }
+            if tasks.len() > MAX_TASKS {
+                let more = format!("{} more in the dashboard", tasks.len() - MAX_TASKS);
+                menu.append(&MenuItem::new(app, more.as_str(), false, None::<&str>)?)?;
+            }
+        }
+    }
+
+    let online = !matches!(snapshot, Snapshot::Offline(_));
+    menu.append(&PredefinedMenuItem::separator(app)?)?;
+    menu.append(&MenuItem::with_id(app, OPEN_ITEM, "Open dashboard", true, None::<&str>)?)?;
+    menu.append(&MenuItem::with_id(app, HEALTH_SCAN_ITEM, "Run health scan", online, None::<&str>)?)?;
+    menu.append(&PredefinedMenuItem::separator(app)?)?;
+    menu.append(&MenuItem::with_id(app, QUIT_ITEM, "Quit and stop backend", true, None::<&str>)?)?;
+    Ok(menu)
+}
+
+/// Show the icon with a red badge while something needs attention.
+fn set_attention(app: &AppHandle, tray: &TrayIcon, attention: bool) -> tauri::Result<()> {
+    let Some(icon) = app.default_window_icon() else {
+        return Ok(());
+    };
+    let icon = if attention { badged(icon) } else { icon.clone().to_owned() };
+    tray.set_icon(Some(icon))
+}
+
+/// Copy of `icon` with a red dot in the top right corner.
+fn badged(icon: &Image<'_>) -> Image<'static> {
+    let (width, height) = (icon.width(), icon.height());
+    let mut rgba = icon.rgba().to_vec();
+    let radius = (width.min(height) / 4) as i64;
+    let (cx, cy) = (width as i64 - radius - 1, radius);
+    for y in 0..height as i64 {
+        for x in 0..width as i64 {
+            if (x - cx).pow(2) + (y - cy).pow(2) <= radius.pow(2) {
+                let i = ((y * width as i64 + x) * 4) as usize;
+                rgba[i..i + 4].copy_from_slice(&[0xe5, 0x39, 0x35, 0xff]);
+            }
+        }
+    }
+    Image::new_owned(rgba, width, height)
+}
+
+fn on_menu_event(app: &AppHandle, event: MenuEvent) {
+    match event.id().as_ref() {
+        OPEN_ITEM => instance::focus_main_window(app),
+        HEALTH_SCAN_ITEM => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move { health_scan(&app).await });
+        }
+        QUIT_ITEM => {
+            info!("quit requested from tray");
+            // The exit handler stops the sidecars
+            app.exit(0);
+        }
+        id => {
+            if let Some(task_id) = id.strip_prefix(TASK_ITEM_PREFIX) {
+                instance::focus_main_window(app);
+                telemetry::emit(
+                    app,
+                    "navigate",
+                    NavigateTarget::Task {
+                        task_id: task_id.to_string(),
+                    },
+                );
+            }
+        }
+    }
+}
+
+async fn health_scan(app: &AppHandle) {
+    let profiles = app.state::<Profiles>();
+    let Some(backend) = profiles.backend(&profiles.default_profile()) else {
+        return;
+    };
+    let client = reqwest::Client::builder()
+        .timeout(HEALTH_SCAN_TIMEOUT)
+        .build()
+        .unwrap_or_default();
+    let result = client
+        .post(format!("{}/api/tasks/health-scan", backend.url()))
+        .send()
+        .await
+        .and_then(|response| response.error_for_status());
+    match result {
+        Ok(_) => info!(profile = %backend.profile, "health scan finished"),
+        Err(e) => warn!(profile = %backend.profile, error = %e, "health scan failed"),
+    }
+}
@@ -0,0 +1,282 @@
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::AppHandle;
+use tokio::sync::mpsc::{self, UnboundedSender};
+use tokio::time::Instant;
+use tracing::{debug, info, info_span, warn, Instrument};
+
+use crate::models::{AgentFinding, AgentProgress};
+use crate::profiles::Backend;
+use crate::telemetry;
+
+/// Quiet period after the last write to a file before it is read
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Longest a file written to continuously waits before it is read anyway
+const MAX_DELAY: Duration = Duration::from_millis(500);
+
+/// How often the workspace bases are checked for changes or for being created
+const RESCAN_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Bytes read from the end of a JSONL file to find its latest entry
+const TAIL_BYTES: u64 = 64 * 1024;
+
+/// Files of a task workspace the watcher reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum FileKind {
+    /// `progress/<agent>_progress.jsonl`
+    Progress,
+    /// `findings/<agent>_findings.jsonl`
+    Findings,
+    /// `AGENT_REGISTRY.json` or `GLOBAL_REGISTRY.json`
+    Registry,
+}
+
+impl FileKind {
+    fn of(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?;
+        let dir = path.parent()?.file_name()?.to_str()?;
+        match name {
+            "AGENT_REGISTRY.json" | "GLOBAL_REGISTRY.json" => Some(Self::Registry),
+            _ if dir == "progress" && name.ends_with("_progress.jsonl") => Some(Self::Progress),
+            _ if dir == "findings" && name.ends_with("_findings.jsonl") => Some(Self::Findings),
+            _ => None,
+        }
+    }
+}
+
+/// Latest content of a changed file.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+enum Entry {
+    Progress(AgentProgress),
+    Finding(AgentFinding),
+    Registry(Value),
+}
+
+/// `workspace-file-changed` payload.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FileChangedPayload {
+    profile: String,
+    kind: FileKind,
+    task_id: Option<String>,
+    agent_id: Option<String>,
+    path: String,
+    removed: bool,
+    /// The file's last entry (the whole document for registries); `None` if removed
+    entry: Option<Entry>,
+}
+
+/// Changes to a file waiting for its writes to settle.
+struct PendingChange {
+    first: Instant,
+    last: Instant,
+}
+
+impl PendingChange {
+    fn due(&self) -> Instant {
+        (self.last + DEBOUNCE).min(self.first + MAX_DELAY)
+    }
+}
+
+/// Watch the profile's workspace bases until the profile is deactivated,
+/// emitting `workspace-file-changed` with the latest entry of every progress,
+/// findings and registry file written to.
+pub(crate) fn run(app: AppHandle, backend: Arc<Backend>) {
+    let span = info_span!("watcher", profile = %backend.profile);
+    let task = async move {
+        let (sender, mut changes) = mpsc::unbounded_channel();
+        let mut watched: Option<(Vec<PathBuf>, RecommendedWatcher)> = None;
+        let mut pending: HashMap<PathBuf, PendingChange> = HashMap::new();
+        let mut rescan = tokio::time::interval(RESCAN_INTERVAL);
+        loop {
+            let due = pending.values().map(PendingChange::due).min();
+            tokio::select! {
+                _ = rescan.tick() => rewatch(&app, &backend, &sender, &mut watched),
+                Some(path) = changes.recv() => {
+                    let now = Instant::now();
+                    pending
+                        .entry(path)
+                        .and_modify(|p| p.last = now)
+                        .or_insert(PendingChange { first: now, last: now });
+                }
+                _ = sleep_until(due) => {
+                    let now = Instant::now();
+                    let settled: Vec<PathBuf> = pending
+                        .iter()
+                        .filter(|(_, p)| p.due() <= now)
+                        .map(|(path, _)| path.clone())
+                        .collect();
+                    for path in &settled {
+                        pending.remove(path);
+                    }
+                    flush(&app, &backend, settled).await;
+                }
+                _ = backend.sidecar.shut_down() => break,
+            }
+        }
+        debug!("workspace watcher stopped");
+    };
+    tauri::async_runtime::spawn(task.instrument(span));
+}
+
+async fn sleep_until(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline).await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Watch the workspace bases again if they changed or appeared since the last check.
+fn rewatch(
+    app: &AppHandle,
+    backend: &Backend,
+    sender: &UnboundedSender<PathBuf>,
+    watched: &mut Option<(Vec<PathBuf>, RecommendedWatcher)>,
+) {
+    let bases = backend.workspace.bases(app);
+    if watched.as_ref().map_or(bases.is_empty(), |(paths, _)| *paths == bases) {
+        return;
+    }
+    *watched = None;
+    if bases.is_empty() {
+        return;
+    }
+
+    let sender = sender.clone();
+    let watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let Ok(event) = event else {
+            return;
+        };
+        if matches!(event.kind, EventKind::Access(_)) {
+            return;
+        }
+        for path in event.paths {
+            if FileKind::of(&path).is_some() {
+                let _ = sender.send(path);
+            }
+        }
+    });
+    let mut watcher = match watcher {
+        Ok(watcher) => watcher,
+        Err(e) => {
+            warn!(error = %e, "cannot watch workspaces");
+            return;
+        }
+    };
+    for base in &bases {
+        match watcher.watch(base, RecursiveMode::Recursive) {
+            Ok(()) => info!(base = %base.display(), "watching workspace"),
+            Err(e) => warn!(base = %base.display(), error = %e, "cannot watch workspace"),
+        }
+    }
+    *watched = Some((bases, watcher));
+}
+
+/// Read the settled files and emit their changes.
+async fn flush(app: &AppHandle, backend: &Backend, paths: Vec<PathBuf>) {
+    let profile = backend.profile.clone();
+    let read = tauri::async_runtime::spawn_blocking(move || {
+        paths
+            .into_iter()
+            .filter_map(|path| change(&profile, path))
+            .collect::<Vec<_>>()
+    });
+    match read.await {
+        Ok(changes) => {
+            for change in changes {
+                telemetry::emit(app, "workspace-file-changed", change);
+            }
+        }
+        Err(e) => warn!(error = %e, "failed to read changed files"),
+    }
+}
+
+fn change(profile: &str, path: PathBuf) -> Option<FileChangedPayload> {
+    let kind = FileKind::of(&path)?;
+    let removed = !path.is_file();
+    let entry = if removed {
+        None
+    } else {
+        match read_entry(kind, &path) {
+            Ok(entry) => Some(entry),
+            Err(e) => {
+                // Usually a write still in progress; the next one is reported again
+                debug!(path = %path.display(), error = %e, "skipping unreadable file");
+                return None;
+            }
+        }
+    };
+
+    let task_id = path
+        .iter()
+        .filter_map(|part| part.to_str())
+        .find(|part| part.starts_with("TASK-"))
+        .map(str::to_string);
+    let agent_id = match kind {
+        FileKind::Registry => None,
+        FileKind::Progress | FileKind::Findings => path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.rsplit_once('_'))
+            .map(|(agent_id, _)| agent_id.to_string()),
+    };
+    Some(FileChangedPayload {
+        profile: profile.to_string(),
+        kind,
+        task_id,
+        agent_id,
+        path: path.display().to_string(),
+        removed,
+        entry,
+    })
+}
+
+fn read_entry(kind: FileKind, path: &Path) -> Result<Entry, String> {
+    match kind {
+        FileKind::Registry => {
+            let content = std::fs::read(path).map_err(|e| e.to_string())?;
+            serde_json::from_slice(&content)
+                .map(Entry::Registry)
+                .map_err(|e| e.to_string())
+        }
+        FileKind::Progress => serde_json::from_str(&last_line(path)?)
+            .map(Entry::Progress)
+            .map_err(|e| e.to_string()),
+        FileKind::Findings => serde_json::from_str(&last_line(path)?)
+            .map(Entry::Finding)
+            .map_err(|e| e.to_string()),
+    }
+}
+
+/// Last complete line of a JSONL file, read from its end.
+fn last_line(path: &Path) -> Result<String, String> {
+    let mut file = File::open(path).map_err(|e| e.to_string())?;
+    let len = file.metadata().map_err(|e| e.to_string())?.len();
+    file.seek(SeekFrom::Start(len.saturating_sub(TAIL_BYTES)))
+        .map_err(|e| e.to_string())?;
+    let mut tail = Vec::new();
+    file.read_to_end(&mut tail).map_err(|e| e.to_string())?;
+
+    // A line without its newline is still being written
+    let complete = match tail.iter().rposition(|&b| b == b'\n') {
+        Some(end) => &tail[..end],
+        None => return Err("no complete line".to_string()),
+    };
+    complete
+        .split(|&b| b == b'\n')
+        .rev()
+        .map(String::from_utf8_lossy)
+        .find(|line| !line.trim().is_empty())
+        .map(|line| line.into_owned())
+        .ok_or_else(|| "no complete line".to_string())
+}
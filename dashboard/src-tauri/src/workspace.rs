@@ -0,0 +1,227 @@
+use rusqlite::{Connection, OpenFlags};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_dialog::DialogExt;
+use tracing::{debug, warn};
+
+use crate::api::ApiError;
+use crate::data::DataSource;
+use crate::models::AgentData;
+
+/// Environment variable the orchestrator and the sidecar read the workspace base from
+pub(crate) const WORKSPACE_ENV: &str = "CLAUDE_ORCHESTRATOR_WORKSPACE";
+
+/// Directory name of a project-local workspace base
+const WORKSPACE_DIR: &str = ".agent-workspace";
+
+/// State database every initialized workspace base has
+const STATE_DB: &[&str] = &["registry", "state.sqlite3"];
+
+/// The orchestrator's registry of every workspace base it has used, under home
+const GLOBAL_REGISTRY: &[&str] = &[".claude-orchestrator", "global_registry.sqlite3"];
+
+/// Workspace bases the orchestrator uses without configuration, under home:
+/// its default and the legacy `~/.agent-workspace`
+const DEFAULT_BASES: &[&[&str]] = &[&[".claude-orchestrator", "workspaces"], &[WORKSPACE_DIR]];
+
+/// Workspace base passed to a profile's sidecar.
+///
+/// `None` leaves the sidecar to its own discovery.
+#[derive(Default)]
+pub(crate) struct Workspace(Mutex<Option<PathBuf>>);
+
+impl Workspace {
+    /// Resolve the workspace base: environment (if `inherit_env`), then the profile's configuration.
+    pub(crate) fn resolve(configured: Option<&Path>, inherit_env: bool) -> Self {
+        let inherited = std::env::var_os(WORKSPACE_ENV).filter(|_| inherit_env);
+        let base = match inherited {
+            // Inherited by the sidecar anyway, so use it as is
+            Some(base) => Some(PathBuf::from(base)),
+            None => configured.and_then(|base| match validate(base) {
+                Ok(base) => Some(base),
+                Err(e) => {
+                    warn!(error = %e, "ignoring saved workspace");
+                    None
+                }
+            }),
+        };
+        Self(Mutex::new(base))
+    }
+
+    pub(crate) fn get(&self) -> Option<PathBuf> {
+        self.0.lock().unwrap().clone()
+    }
+
+    /// Existing workspace bases holding the profile's tasks: the chosen one,
+    /// else every base the backend searches (see `known_bases`).
+    pub(crate) fn bases(&self, app: &AppHandle) -> Vec<PathBuf> {
+        match self.get() {
+            Some(base) => vec![base].into_iter().filter(|base| base.is_dir()).collect(),
+            None => app.path().home_dir().map(|home| known_bases(&home)).unwrap_or_default(),
+        }
+    }
+
+    pub(crate) fn set(&self, base: PathBuf) {
+        *self.0.lock().unwrap() = Some(base);
+    }
+}
+
+/// Check that `path` is an initialized workspace base and return its canonical form.
+///
+/// A project directory containing `.agent-workspace` is accepted for the base inside it.
+pub(crate) fn validate(path: &Path) -> Result<PathBuf, String> {
+    let path = path
+        .canonicalize()
+        .map_err(|e| format!("Cannot open {}: {}", path.display(), e))?;
+    if !path.is_dir() {
+        return Err(format!("{} is not a directory", path.display()));
+    }
+
+    let nested = path.join(WORKSPACE_DIR);
+    let base = if has_state_db(&path) || !nested.is_dir() {
+        path
+    } else {
+        nested
+    };
+
+    if !has_state_db(&base) {
+        return Err(format!(
+            "{} is not an orchestrator workspace (missing {})",
+            base.display(),
+            STATE_DB.join("/")
+        ));
+    }
+    Ok(base)
+}
+
+fn has_state_db(base: &Path) -> bool {
+    STATE_DB.iter().fold(base.to_path_buf(), |path, part| path.join(part)).is_file()
+}
+
+/// Existing workspace bases the backend's `_iter_workspace_bases` searches:
+/// the active ones of the orchestrator's global registry, and its default
+/// locations, which the backend registers when it finds them.
+pub(crate) fn known_bases(home: &Path) -> Vec<PathBuf> {
+    let registry = GLOBAL_REGISTRY.iter().fold(home.to_path_buf(), |path, part| path.join(part));
+    let registered = registered_bases(&registry).unwrap_or_else(|e| {
+        debug!(registry = %registry.display(), error = %e, "cannot read the global registry");
+        Vec::new()
+    });
+    let defaults = DEFAULT_BASES
+        .iter()
+        .map(|parts| parts.iter().fold(home.to_path_buf(), |path, part| path.join(part)));
+
+    let mut bases: Vec<PathBuf> = Vec::new();
+    for base in registered.into_iter().chain(defaults) {
+        // The registry may list a base by another path than its default
+        let Ok(base) = base.canonicalize() else {
+            continue;
+        };
+        if base.is_dir() && !bases.contains(&base) {
+            bases.push(base);
+        }
+    }
+    bases
+}
+
+fn registered_bases(registry: &Path) -> rusqlite::Result<Vec<PathBuf>> {
+    if !registry.is_file() {
+        return Ok(Vec::new());
+    }
+    let conn = Connection::open_with_flags(
+        registry,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )?;
+    let mut statement =
+        conn.prepare("SELECT workspace_base FROM known_workspaces WHERE is_active = 1 ORDER BY last_accessed DESC")?;
+    let rows = statement.query_map([], |row| row.get::<_, String>(0).map(PathBuf::from))?;
+    rows.collect()
+}
+
+/// Files of an agent, as in its `tracked_files`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum AgentFile {
+    Prompt,
+    Log,
+    Progress,
+    Findings,
+    DeployLog,
+}
+
+/// Where `file` of agent `agent_id` is: its tracked path, relative ones being
+/// under the task workspace, else where the orchestrator writes it, looked up
+/// under the task's `archive/` if moved there. The file may not exist yet.
+pub(crate) async fn agent_file(
+    source: &DataSource,
+    task_id: &str,
+    agent_id: &str,
+    file: AgentFile,
+) -> Result<PathBuf, ApiError> {
+    let task = source.task(task_id).await?;
+    let workspace = PathBuf::from(&task.workspace);
+    let agent = task
+        .agents
+        .iter()
+        .find(|agent| agent.id == agent_id)
+        .ok_or_else(|| ApiError::Status {
+            status: 404,
+            message: format!("Agent {} not found in task {}", agent_id, task_id),
+        })?;
+    let path = tracked(agent, file)
+        .map(|path| workspace.join(path))
+        .or_else(|| default_path(&workspace, agent_id, file))
+        .ok_or_else(|| format!("Agent {} has no tracked {:?} file", agent_id, file))?;
+
+    let archived = path
+        .file_name()
+        .map(|name| workspace.join("archive").join(name))
+        .filter(|archived| !path.exists() && archived.exists());
+    Ok(archived.unwrap_or(path))
+}
+
+fn tracked(agent: &AgentData, file: AgentFile) -> Option<&str> {
+    let files = &agent.tracked_files;
+    match file {
+        AgentFile::Prompt => files.prompt_file.as_deref(),
+        AgentFile::Log => files.log_file.as_deref(),
+        AgentFile::Progress => files.progress_file.as_deref(),
+        AgentFile::Findings => files.findings_file.as_deref(),
+        AgentFile::DeployLog => files.deploy_log.as_deref(),
+    }
+    .filter(|path| !path.is_empty())
+}
+
+fn default_path(workspace: &Path, agent_id: &str, file: AgentFile) -> Option<PathBuf> {
+    match file {
+        AgentFile::Log => Some(workspace.join("logs").join(format!("{}_stream.jsonl", agent_id))),
+        AgentFile::Progress => Some(workspace.join("progress").join(format!("{}_progress.jsonl", agent_id))),
+        AgentFile::Findings => Some(workspace.join("findings").join(format!("{}_findings.jsonl", agent_id))),
+        AgentFile::Prompt | AgentFile::DeployLog => None,
+    }
+}
+
+/// Ask the user for a workspace directory with the native folder picker.
+///
+/// Returns `None` if the dialog was cancelled.
+pub(crate) async fn pick(app: &AppHandle, current: Option<&Path>) -> Option<PathBuf> {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    let mut dialog = app.dialog().file().set_title("Choose orchestrator workspace");
+    if let Some(current) = current {
+        dialog = dialog.set_directory(current);
+    }
+    dialog.pick_folder(move |folder| {
+        let _ = tx.send(folder);
+    });
+
+    let folder = rx.await.ok()??;
+    match folder.into_path() {
+        Ok(path) => Some(path),
+        Err(e) => {
+            warn!(error = %e, "folder picker returned an unusable path");
+            None
+        }
+    }
+}
@@ -150,6 +150,21 @@ class HealthDaemon:
                 logger.error(f"Error scanning task {task_id}: {e}")
         return True
 
+    def scan_task(self, task_id: str) -> bool:
+        """
+        Scan the health of one task's agents once, whether or not the daemon is running.
+
+        Args:
+            task_id: Task ID to scan
+
+        Returns:
+            True if the task was scanned, False if its workspace was not found.
+        """
+        if not find_task_workspace(task_id):
+            return False
+        self._scan_task_health(task_id)
+        return True
+
     def _daemon_loop(self):
         """Main daemon loop that continuously monitors health."""
         logger.info("HealthDaemon loop started")
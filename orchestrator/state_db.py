@@ -136,6 +136,17 @@ def _connect(db_path: str) -> sqlite3.Connection:
     return conn
 
 
+def _connect_read_only(workspace_base: str) -> Optional[sqlite3.Connection]:
+    """Open the state database read-only, without creating or migrating it; None if it doesn't exist."""
+    db_path = Path(workspace_base).resolve() / "registry" / "state.sqlite3"
+    if not db_path.is_file():
+        return None
+    conn = sqlite3.connect(f"{db_path.as_uri()}?mode=ro", uri=True, timeout=10)
+    conn.row_factory = sqlite3.Row
+    conn.execute("PRAGMA busy_timeout=10000;")
+    return conn
+
+
 def _init_db(conn: sqlite3.Connection) -> None:
     """Initialize database schema with all required tables and indexes."""
 
@@ -882,6 +893,7 @@ def get_all_tasks(
                 COUNT(DISTINCT a.agent_id) as total_agents,
                 SUM(CASE WHEN a.status IN ('running', 'working', 'blocked', 'reviewing') THEN 1 ELSE 0 END) as active_agents,
                 SUM(CASE WHEN a.status = 'completed' THEN 1 ELSE 0 END) as completed_agents,
+                SUM(CASE WHEN a.status IN ('failed', 'error', 'terminated', 'killed') THEN 1 ELSE 0 END) as failed_agents,
                 (SELECT name FROM phases WHERE task_id = t.task_id AND phase_index = t.current_phase_index) as current_phase_name,
                 (SELECT status FROM phases WHERE task_id = t.task_id AND phase_index = t.current_phase_index) as current_phase_status
             FROM tasks t
@@ -909,6 +921,7 @@ def get_all_tasks(
             task["total_agents"] = task["total_agents"] or 0
             task["active_agents"] = task["active_agents"] or 0
             task["completed_agents"] = task["completed_agents"] or 0
+            task["failed_agents"] = task["failed_agents"] or 0
             tasks.append(task)
 
         return tasks
@@ -1730,6 +1743,41 @@ def get_latest_handover(
         conn.close()
 
 
+def get_handovers_for_task(*, workspace_base: str, task_id: str) -> List[Dict[str, Any]]:
+    """Get all handover documents of a task, oldest first."""
+    conn = _connect_read_only(workspace_base)
+    if conn is None:
+        return []
+    try:
+        try:
+            rows = conn.execute(
+                """
+                SELECT * FROM handovers
+                WHERE task_id = ?
+                ORDER BY from_phase_index ASC, created_at ASC
+                """,
+                (task_id,)
+            ).fetchall()
+        except sqlite3.OperationalError as e:
+            # Databases from before handovers have no such table
+            if "no such table" in str(e):
+                return []
+            raise
+
+        handovers = []
+        for row in rows:
+            handover = dict(row)
+            for field in ("key_findings", "blockers", "recommendations"):
+                try:
+                    handover[field] = json.loads(handover[field]) if handover.get(field) else []
+                except Exception:
+                    handover[field] = []
+            handovers.append(handover)
+        return handovers
+    finally:
+        conn.close()
+
+
 # ============================================================================
 # PHASE OUTCOMES CRUD FUNCTIONS (Context Accumulator - Jan 2026)
 # ============================================================================
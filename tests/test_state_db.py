@@ -39,6 +39,9 @@ from orchestrator.state_db import (
     finalize_review,
     transition_task_to_completed,
     get_state_db_path,
+    get_all_tasks,
+    create_handover,
+    get_handovers_for_task,
     AGENT_TERMINAL_STATUSES,
     AGENT_ACTIVE_STATUSES,
     _connect,
@@ -693,6 +696,120 @@ class TestStateDB:
         # Terminal (completed + failed + error + terminated) should be 6
         assert counts["terminal"] == 6, f"Expected 6 terminal, got {counts['terminal']}"
 
+    def test_get_all_tasks_counts_failed_agents(self, temp_workspace):
+        """Test that get_all_tasks reports failed, errored, terminated and killed agents."""
+        db_path = ensure_db(temp_workspace)
+        now = datetime.now().isoformat()
+
+        conn = _connect(db_path)
+        try:
+            for task_id in ("TASK-failed-test", "TASK-no-agents"):
+                conn.execute("""
+                    INSERT INTO tasks(task_id, workspace, workspace_base, status, created_at, updated_at)
+                    VALUES(?, ?, ?, ?, ?, ?)
+                """, (task_id, "workspace", temp_workspace, "ACTIVE", now, now))
+
+            agent_configs = [
+                ("agent-w1", "working"),
+                ("agent-c1", "completed"),
+                ("agent-f1", "failed"),
+                ("agent-e1", "error"),
+                ("agent-t1", "terminated"),
+                ("agent-k1", "killed"),
+            ]
+            for agent_id, status in agent_configs:
+                conn.execute("""
+                    INSERT INTO agents(agent_id, task_id, type, status, progress, phase_index, started_at)
+                    VALUES(?, ?, ?, ?, ?, ?, ?)
+                """, (agent_id, "TASK-failed-test", "worker", status, 0, 0, now))
+        finally:
+            conn.close()
+
+        tasks = {task["task_id"]: task for task in get_all_tasks(workspace_base=temp_workspace)}
+
+        failed = tasks["TASK-failed-test"]
+        assert failed["total_agents"] == 6
+        assert failed["active_agents"] == 1
+        assert failed["completed_agents"] == 1
+        assert failed["failed_agents"] == 4, f"Expected 4 failed, got {failed['failed_agents']}"
+
+        # Tasks without agents report zero rather than NULL
+        assert tasks["TASK-no-agents"]["failed_agents"] == 0
+        assert tasks["TASK-no-agents"]["total_agents"] == 0
+
+    def test_get_handovers_for_task(self, temp_workspace):
+        """Test that handovers are returned oldest phase first with their lists decoded."""
+        db_path = ensure_db(temp_workspace)
+        task_id = "TASK-handover-test"
+        now = datetime.now().isoformat()
+
+        conn = _connect(db_path)
+        try:
+            for tid in (task_id, "TASK-other"):
+                conn.execute("""
+                    INSERT INTO tasks(task_id, workspace, workspace_base, status, created_at, updated_at)
+                    VALUES(?, ?, ?, ?, ?, ?)
+                """, (tid, "workspace", temp_workspace, "ACTIVE", now, now))
+        finally:
+            conn.close()
+
+        create_handover(
+            workspace_base=temp_workspace,
+            task_id=task_id,
+            from_phase_index=1,
+            to_phase_index=2,
+            summary="Fix applied",
+        )
+        create_handover(
+            workspace_base=temp_workspace,
+            task_id=task_id,
+            from_phase_index=0,
+            to_phase_index=1,
+            summary="Root cause found",
+            key_findings=["KeyError on missing phase_index"],
+            recommendations=["Default phase_index to 0"],
+        )
+        create_handover(
+            workspace_base=temp_workspace,
+            task_id="TASK-other",
+            from_phase_index=0,
+            to_phase_index=1,
+            summary="Other task",
+        )
+
+        handovers = get_handovers_for_task(workspace_base=temp_workspace, task_id=task_id)
+
+        assert [h["summary"] for h in handovers] == ["Root cause found", "Fix applied"]
+        assert handovers[0]["key_findings"] == ["KeyError on missing phase_index"]
+        assert handovers[0]["blockers"] == []
+        assert handovers[0]["recommendations"] == ["Default phase_index to 0"]
+        assert handovers[1]["key_findings"] == []
+
+    def test_get_handovers_for_task_does_not_create_database(self, temp_workspace):
+        """Test that reading handovers leaves a workspace without a state database untouched."""
+        handovers = get_handovers_for_task(workspace_base=temp_workspace, task_id="TASK-missing")
+
+        assert handovers == []
+        assert not os.path.exists(os.path.join(temp_workspace, "registry"))
+
+    def test_get_handovers_for_task_with_old_schema(self, temp_workspace):
+        """Test that a state database from before handovers is read without migrating it."""
+        db_dir = os.path.join(temp_workspace, "registry")
+        os.makedirs(db_dir)
+        db_path = os.path.join(db_dir, "state.sqlite3")
+        conn = sqlite3.connect(db_path)
+        conn.execute("CREATE TABLE tasks (task_id TEXT PRIMARY KEY)")
+        conn.commit()
+        conn.close()
+
+        handovers = get_handovers_for_task(workspace_base=temp_workspace, task_id="TASK-old")
+
+        assert handovers == []
+        conn = sqlite3.connect(db_path)
+        tables = {row[0] for row in conn.execute("SELECT name FROM sqlite_master WHERE type='table'")}
+        conn.close()
+        assert tables == {"tasks"}
+
     def test_edge_case_empty_database(self, temp_workspace):
         """Test behavior with empty database."""
         db_path = ensure_db(temp_workspace)